@@ -1,6 +1,8 @@
 use crate::{
     bot::{
-        state::{AppState, PendingQAInfo, QAStatus},
+        dialogue::{DialogueAction, DialogueInput, PendingQAInfo, QAStatus},
+        hooks::{HookContext, HookOutcome, HookRegistry, UpdateKind},
+        state::AppState,
         types::CallbackData,
         ui,
         utils::{bold, combine_texts, ensure_blockquote, is_admin},
@@ -21,6 +23,7 @@ pub async fn callback_handler(
     callback_query: CallbackQuery,
     state: Arc<Mutex<AppState>>,
     qa_service: Arc<Mutex<QAService>>,
+    hook_registry: Arc<HookRegistry>,
 ) -> Result<(), anyhow::Error> {
     let (user, message, data, config) = {
         let service_guard = qa_service.lock().await;
@@ -35,6 +38,26 @@ pub async fn callback_handler(
         }
     };
 
+    let hook_ctx = HookContext {
+        kind: UpdateKind::Callback,
+        chat_id: message.chat().id,
+        user_id: Some(user.id),
+        is_private: message.chat().id.is_user(),
+        config: config.clone(),
+    };
+    if let HookOutcome::Reject { reason } = hook_registry.run_pre(&hook_ctx).await {
+        log::info!(
+            "Hook rejected callback in chat {}: {}",
+            message.chat().id,
+            reason
+        );
+        bot.answer_callback_query(callback_query.id).await?;
+        return Ok(());
+    }
+    hook_registry.run_post(&hook_ctx).await;
+
+    // Action-specific authorization, stricter than (and independent of) the
+    // hook gate above: only admins may act on QA management buttons at all.
     if !is_admin(&bot, message.chat().id, user.id, &config).await {
         bot.answer_callback_query(callback_query.id)
             .text("Only administrators can perform this action.")
@@ -43,10 +66,10 @@ pub async fn callback_handler(
         return Ok(());
     }
 
-    let callback_data: CallbackData = match serde_json::from_str(&data) {
+    let callback_data = match CallbackData::decode(&data) {
         Ok(data) => data,
         Err(e) => {
-            log::error!("Failed to deserialize callback data: {}. Data: {}", e, data);
+            log::error!("Failed to decode callback data: {}. Data: {}", e, data);
             return Ok(());
         }
     };
@@ -55,7 +78,12 @@ pub async fn callback_handler(
 
     match callback_data.clone() {
         CallbackData::ViewQa { short_hash } => {
-            state.lock().await.pending_qas.remove(&pending_qa_key);
+            state
+                .lock()
+                .await
+                .dialogue_store
+                .remove(&pending_qa_key)
+                .await?;
             let service_guard = qa_service.lock().await;
             if let Some((item, _)) = service_guard.find_by_short_hash(&short_hash) {
                 let display_question = ensure_blockquote(
@@ -79,6 +107,17 @@ pub async fn callback_handler(
             }
             return Ok(());
         }
+        CallbackData::ListPage { page } => {
+            let service_guard = qa_service.lock().await;
+            let all_qas = service_guard.get_all_qa_items();
+            let items = crate::bot::commands::qa_list_items(&all_qas);
+            let keyboard =
+                ui::qa_list_keyboard(&items, page, crate::bot::commands::QA_LIST_PAGE_SIZE);
+            bot.edit_message_reply_markup(message.chat().id, message.id())
+                .reply_markup(keyboard)
+                .await?;
+            return Ok(());
+        }
         CallbackData::DeletePrompt { short_hash } => {
             let keyboard = ui::delete_confirmation_keyboard(&short_hash);
             bot.edit_message_text(
@@ -119,7 +158,6 @@ pub async fn callback_handler(
         | CallbackData::EditAnswerPrompt { short_hash } => {
             let service_guard = qa_service.lock().await;
             if let Some((item, full_hash)) = service_guard.find_by_short_hash(&short_hash) {
-                let mut state_guard = state.lock().await;
                 let (new_status, prompt_text) =
                     if matches!(callback_data, CallbackData::EditQuestionPrompt { .. }) {
                         (
@@ -139,9 +177,12 @@ pub async fn callback_handler(
                         )
                     };
 
-                state_guard
-                    .pending_qas
-                    .insert(pending_qa_key, PendingQAInfo { status: new_status });
+                state
+                    .lock()
+                    .await
+                    .dialogue_store
+                    .set(pending_qa_key, PendingQAInfo::new(new_status))
+                    .await?;
 
                 let keyboard = ui::cancel_edit_keyboard(&short_hash);
                 bot.edit_message_text(message.chat().id, message.id(), prompt_text)
@@ -154,8 +195,7 @@ pub async fn callback_handler(
         _ => {}
     }
 
-    let mut state_guard = state.lock().await;
-    let pending_qa = match state_guard.pending_qas.get_mut(&pending_qa_key) {
+    let pending_qa = match state.lock().await.dialogue_store.get(&pending_qa_key).await? {
         Some(info) => info,
         None => {
             bot.answer_callback_query(callback_query.id).await?;
@@ -170,61 +210,93 @@ pub async fn callback_handler(
             bot.answer_callback_query(callback_query.id).await?;
             bot.edit_message_text(message.chat().id, message.id(), "❌ Action Cancelled.")
                 .await?;
-            state_guard.pending_qas.remove(&pending_qa_key);
+            state
+                .lock()
+                .await
+                .dialogue_store
+                .remove(&pending_qa_key)
+                .await?;
         }
         CallbackData::Reedit => {
-            if let QAStatus::Confirmation { question, .. } = pending_qa.status.clone() {
-                pending_qa.status = QAStatus::Answer {
-                    question: question.clone(),
-                };
-                bot.answer_callback_query(callback_query.id).await?;
+            let (next_status, actions) =
+                crate::bot::dialogue::advance(&pending_qa.status, DialogueInput::Reedit);
+            bot.answer_callback_query(callback_query.id).await?;
 
-                let display_question =
-                    ensure_blockquote(question.clone(), MessageEntityKind::ExpandableBlockquote);
+            for action in actions {
+                if let DialogueAction::Show = action {
+                    if let Some(QAStatus::Answer { question }) = &next_status {
+                        let display_question = ensure_blockquote(
+                            question.clone(),
+                            MessageEntityKind::ExpandableBlockquote,
+                        );
 
-                let header = bold("❓ Question\n\n");
-                let footer = FormattedText {
-                    text: "\n\nPlease reply to this message with the new answer.".to_string(),
-                    entities: vec![],
-                };
+                        let header = bold("❓ Question\n\n");
+                        let footer = FormattedText {
+                            text: "\n\nPlease reply to this message with the new answer."
+                                .to_string(),
+                            entities: vec![],
+                        };
 
-                let combined = combine_texts(&[&header, &display_question, &footer]);
+                        let combined = combine_texts(&[&header, &display_question, &footer]);
 
-                bot.edit_message_text(message.chat().id, message.id(), combined.text)
-                    .entities(combined.entities)
-                    .reply_markup(ui::reedit_keyboard())
+                        bot.edit_message_text(message.chat().id, message.id(), combined.text)
+                            .entities(combined.entities)
+                            .reply_markup(ui::reedit_keyboard())
+                            .await?;
+                    }
+                }
+            }
+
+            if let Some(status) = next_status {
+                state
+                    .lock()
+                    .await
+                    .dialogue_store
+                    .set(pending_qa_key, PendingQAInfo::new(status))
                     .await?;
             }
         }
         CallbackData::Confirm => {
-            if let QAStatus::Confirmation { question, answer } = pending_qa.status.clone() {
-                bot.answer_callback_query(callback_query.id)
-                    .text("Saving...")
-                    .await?;
-
-                drop(state_guard);
+            let (_, actions) =
+                crate::bot::dialogue::advance(&pending_qa.status, DialogueInput::Confirm);
+            bot.answer_callback_query(callback_query.id)
+                .text("Saving...")
+                .await?;
 
-                let mut service_guard = qa_service.lock().await;
-                match service_guard.add_qa(&question, &answer).await {
-                    Ok(_) => {
-                        bot.edit_message_text(
-                            message.chat().id,
-                            message.id(),
-                            "✅ QA pair added successfully!",
-                        )
-                        .await?;
+            for action in actions {
+                match action {
+                    DialogueAction::SaveNew { question, answer } => {
+                        let mut service_guard = qa_service.lock().await;
+                        match service_guard.add_qa(&question, &answer).await {
+                            Ok(_) => {
+                                bot.edit_message_text(
+                                    message.chat().id,
+                                    message.id(),
+                                    "✅ QA pair added successfully!",
+                                )
+                                .await?;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to add QA: {:?}", e);
+                                bot.edit_message_text(
+                                    message.chat().id,
+                                    message.id(),
+                                    format!("Error saving QA: {}", e),
+                                )
+                                .await?;
+                            }
+                        }
                     }
-                    Err(e) => {
-                        log::error!("Failed to add QA: {:?}", e);
-                        bot.edit_message_text(
-                            message.chat().id,
-                            message.id(),
-                            format!("Error saving QA: {}", e),
-                        )
-                        .await?;
+                    DialogueAction::End => {
+                        state
+                            .lock()
+                            .await
+                            .dialogue_store
+                            .remove(&pending_qa_key)
+                            .await?;
                     }
+                    _ => {}
                 }
-                state.lock().await.pending_qas.remove(&pending_qa_key);
             }
         }
         _ => {}
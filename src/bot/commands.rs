@@ -1,9 +1,11 @@
-use crate::bot::state::{AppState, PendingQAInfo, QAStatus};
+use crate::bot::moderation;
+use crate::bot::dialogue::{PendingQAInfo, QAStatus};
+use crate::bot::hooks::{HookContext, HookOutcome, HookRegistry, UpdateKind};
+use crate::bot::state::AppState;
 use crate::bot::ui;
-use crate::bot::utils::{
-    bold, combine_texts, ensure_blockquote, is_admin, is_super_admin, schedule_message_deletion,
-};
-use crate::config::Config;
+use crate::bot::utils::{bold, combine_texts, ensure_blockquote, is_admin, schedule_message_deletion};
+use crate::config::{self, Config};
+use crate::i18n::{Locale, MessageId, t};
 use crate::qa::types::{FormattedText, QAItem};
 use crate::qa::{QAService, get_question_hash, search};
 use chrono::{Duration, Utc};
@@ -26,8 +28,8 @@ pub enum Command {
     AddQA,
     #[command(description = "回复消息以查找答案。")]
     Answer,
-    #[command(description = "列出所有问答以进行管理。")]
-    ListQA,
+    #[command(description = "浏览所有问答以进行管理（支持翻页）。")]
+    List,
     #[command(description = "按关键字搜索问答。", parse_with = "split")]
     SearchQA(String),
     #[command(
@@ -37,6 +39,21 @@ pub enum Command {
     Snooze(String),
     #[command(description = "立即恢复机器人自动回复。")]
     Resume,
+    #[command(
+        description = "回复一段长文本以将其分块并导入为可检索的问答来源。",
+        parse_with = "split"
+    )]
+    Ingest(String),
+    #[command(description = "切换本群使用的语言（zh 或 en）。", parse_with = "split")]
+    Lang(String),
+    #[command(description = "查看问答库、向量模型和 API Key 池的运行状态。")]
+    Stats,
+    #[command(description = "回复被禁言用户的消息，提前解除其禁言。")]
+    Pardon,
+    #[command(description = "立即重新加载配置文件和问答数据，无需等待下一次轮询。")]
+    Reload,
+    #[command(description = "清空本群的多轮对话上下文。")]
+    Clear,
 }
 
 /// Main command handler that dispatches to specific handlers.
@@ -46,31 +63,33 @@ pub async fn command_handler(
     command: Command,
     state: Arc<Mutex<AppState>>,
     qa_service: Arc<Mutex<QAService>>,
+    hook_registry: Arc<HookRegistry>,
 ) -> Result<(), anyhow::Error> {
     let chat_id = message.chat.id;
     let user_id = message.from.as_ref().map(|u| u.id);
 
     let config = qa_service.lock().await.config.clone();
+    let locale = state
+        .lock()
+        .await
+        .locale_for(chat_id, config.i18n.default_locale);
 
-    if chat_id.is_user() {
-        if let Some(uid) = user_id {
-            if !is_super_admin(uid, &config) {
-                bot.send_message(chat_id, "您无权在私聊中使用命令。")
-                    .await?;
-                return Ok(());
-            }
-        } else {
-            return Ok(());
+    let hook_ctx = HookContext {
+        kind: UpdateKind::Command,
+        chat_id,
+        user_id,
+        is_private: chat_id.is_user(),
+        config: config.clone(),
+    };
+    if let HookOutcome::Reject { reason } = hook_registry.run_pre(&hook_ctx).await {
+        log::info!("Hook rejected command in chat {}: {}", chat_id, reason);
+        if chat_id.is_user() && user_id.is_some() {
+            bot.send_message(chat_id, t(locale, &MessageId::NoPermissionPrivate))
+                .await?;
         }
-    }
-
-    if !chat_id.is_user()
-        && !config.telegram.allowed_group_ids.is_empty()
-        && !config.telegram.allowed_group_ids.contains(&chat_id.0)
-    {
-        log::warn!("Ignoring command from unauthorized group: {}", chat_id);
         return Ok(());
     }
+    hook_registry.run_post(&hook_ctx).await;
 
     schedule_message_deletion(bot.clone(), config.clone(), message.clone());
 
@@ -80,46 +99,82 @@ pub async fn command_handler(
         false
     };
 
-    let admin_only_handler = |bot: Bot, chat_id: ChatId, config: Arc<Config>| async move {
+    let admin_only_handler = |bot: Bot, chat_id: ChatId, config: Arc<Config>, locale: Locale| async move {
         let sent = bot
-            .send_message(chat_id, "只有管理员才能使用此命令。")
+            .send_message(chat_id, t(locale, &MessageId::AdminOnly))
             .await?;
         schedule_message_deletion(bot, config, sent);
         Ok(())
     };
 
     match command {
-        Command::Start => handle_start(bot, chat_id, config).await?,
-        Command::Answer => handle_answer(bot, message, qa_service, state).await?,
+        Command::Start => handle_start(bot, chat_id, config, locale).await?,
+        Command::Answer => handle_answer(bot, message, qa_service, state, locale).await?,
         Command::AddQA => {
             if !is_user_admin {
-                return admin_only_handler(bot, chat_id, config).await;
+                return admin_only_handler(bot, chat_id, config, locale).await;
             }
-            handle_add_qa(bot, message, state).await?
+            handle_add_qa(bot, message, state, config, locale).await?
         }
-        Command::ListQA => {
+        Command::List => {
             if !is_user_admin {
-                return admin_only_handler(bot, chat_id, config).await;
+                return admin_only_handler(bot, chat_id, config, locale).await;
             }
-            handle_list_qa(bot, message, qa_service, state).await?
+            handle_list_qa(bot, message, qa_service, state, locale).await?
         }
         Command::SearchQA(keywords) => {
             if !is_user_admin {
-                return admin_only_handler(bot, chat_id, config).await;
+                return admin_only_handler(bot, chat_id, config, locale).await;
             }
-            handle_search_qa(bot, message, keywords, qa_service, state).await?
+            handle_search_qa(bot, message, keywords, qa_service, state, locale).await?
         }
         Command::Snooze(minutes_str) => {
             if !is_user_admin {
-                return admin_only_handler(bot, chat_id, config).await;
+                return admin_only_handler(bot, chat_id, config, locale).await;
             }
-            handle_snooze(bot, chat_id, minutes_str, state, config).await?
+            handle_snooze(bot, chat_id, minutes_str, state, config, locale).await?
         }
         Command::Resume => {
             if !is_user_admin {
-                return admin_only_handler(bot, chat_id, config).await;
+                return admin_only_handler(bot, chat_id, config, locale).await;
+            }
+            handle_resume(bot, chat_id, state, config, locale).await?
+        }
+        Command::Ingest(source_name) => {
+            if !is_user_admin {
+                return admin_only_handler(bot, chat_id, config, locale).await;
+            }
+            handle_ingest(bot, message, source_name, qa_service, locale).await?
+        }
+        Command::Lang(code) => {
+            if !is_user_admin {
+                return admin_only_handler(bot, chat_id, config, locale).await;
+            }
+            handle_lang(bot, chat_id, code, state, locale).await?
+        }
+        Command::Stats => {
+            if !is_user_admin {
+                return admin_only_handler(bot, chat_id, config, locale).await;
+            }
+            handle_stats(bot, chat_id, qa_service, state, locale).await?
+        }
+        Command::Pardon => {
+            if !is_user_admin {
+                return admin_only_handler(bot, chat_id, config, locale).await;
             }
-            handle_resume(bot, chat_id, state, config).await?
+            handle_pardon(bot, message, state, locale).await?
+        }
+        Command::Reload => {
+            if !is_user_admin {
+                return admin_only_handler(bot, chat_id, config, locale).await;
+            }
+            handle_reload(bot, chat_id, qa_service, locale).await?
+        }
+        Command::Clear => {
+            if !is_user_admin {
+                return admin_only_handler(bot, chat_id, config, locale).await;
+            }
+            handle_clear(bot, chat_id, state, locale).await?
         }
     }
     Ok(())
@@ -130,10 +185,11 @@ async fn check_qa_ready(
     chat_id: ChatId,
     state: Arc<Mutex<AppState>>,
     config: Arc<Config>,
+    locale: Locale,
 ) -> Result<bool, anyhow::Error> {
     if !state.lock().await.is_qa_ready {
         let sent = bot
-            .send_message(chat_id, "⌛️ 问答系统正在初始化，请稍后再试...")
+            .send_message(chat_id, t(locale, &MessageId::QaInitializing))
             .await?;
         schedule_message_deletion(bot, config, sent);
         return Ok(false);
@@ -141,6 +197,22 @@ async fn check_qa_ready(
     Ok(true)
 }
 
+/// How many QA items `/list` shows per page of `ui::qa_list_keyboard`.
+pub(crate) const QA_LIST_PAGE_SIZE: usize = 9;
+
+/// Builds the `(short_hash, question_preview)` pairs `ui::qa_list_keyboard`
+/// needs, shared between the initial `/list` render and `CallbackData::ListPage`
+/// navigation in `callbacks.rs`.
+pub(crate) fn qa_list_items(list: &[QAItem]) -> Vec<(String, String)> {
+    list.iter()
+        .map(|item| {
+            let question_hash = get_question_hash(&item.question.text);
+            let preview = item.question.text.chars().take(40).collect::<String>();
+            (question_hash[..16].to_string(), preview)
+        })
+        .collect()
+}
+
 fn make_qa_keyboard(list: &[QAItem]) -> InlineKeyboardMarkup {
     let buttons: Vec<Vec<InlineKeyboardButton>> = list
         .iter()
@@ -148,10 +220,10 @@ fn make_qa_keyboard(list: &[QAItem]) -> InlineKeyboardMarkup {
             let question_hash = get_question_hash(&item.question.text);
             let short_question = item.question.text.chars().take(40).collect::<String>();
             let short_hash = &question_hash[..16];
-            let callback_data = serde_json::to_string(&crate::bot::types::CallbackData::ViewQa {
+            let callback_data = crate::bot::types::CallbackData::ViewQa {
                 short_hash: short_hash.to_string(),
-            })
-            .unwrap_or_default();
+            }
+            .encode();
             vec![InlineKeyboardButton::callback(
                 short_question,
                 callback_data,
@@ -161,9 +233,14 @@ fn make_qa_keyboard(list: &[QAItem]) -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(buttons)
 }
 
-async fn handle_start(bot: Bot, chat_id: ChatId, config: Arc<Config>) -> Result<(), anyhow::Error> {
+async fn handle_start(
+    bot: Bot,
+    chat_id: ChatId,
+    config: Arc<Config>,
+    locale: Locale,
+) -> Result<(), anyhow::Error> {
     let sent_message = bot
-        .send_message(chat_id, "您好！我已经准备好回答您的问题了。")
+        .send_message(chat_id, t(locale, &MessageId::Welcome))
         .await?;
     schedule_message_deletion(bot, config, sent_message);
     Ok(())
@@ -173,27 +250,42 @@ async fn handle_add_qa(
     bot: Bot,
     message: Message,
     state: Arc<Mutex<AppState>>,
+    config: Arc<Config>,
+    locale: Locale,
 ) -> Result<(), anyhow::Error> {
     let replied_to_message = match message.reply_to_message() {
         Some(m) => m,
         None => {
+            bot.send_message(message.chat.id, t(locale, &MessageId::AddQaNeedsReply))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let question_text = match crate::ocr::extract_text(&bot, replied_to_message, &config).await {
+        Ok(Some(text)) => text,
+        Ok(None) => {
+            bot.send_message(message.chat.id, t(locale, &MessageId::AddQaNeedsText))
+                .await?;
+            return Ok(());
+        }
+        Err(e) => {
             bot.send_message(
                 message.chat.id,
-                "请通过回复您想设置为问题的消息来使用此命令。",
+                t(
+                    locale,
+                    &MessageId::AddQaOcrFailed {
+                        error: e.to_string(),
+                    },
+                ),
             )
             .await?;
             return Ok(());
         }
     };
-    let question_text = replied_to_message.text().unwrap_or_default();
-    if question_text.is_empty() {
-        bot.send_message(message.chat.id, "被回复的消息必须包含文本才能用作问题。")
-            .await?;
-        return Ok(());
-    }
 
     let question_from_reply = FormattedText {
-        text: question_text.to_string(),
+        text: question_text,
         entities: replied_to_message.entities().unwrap_or_default().to_vec(),
     };
 
@@ -202,10 +294,9 @@ async fn handle_add_qa(
         MessageEntityKind::ExpandableBlockquote,
     );
 
-    // 使用我们新的格式化工具来构建消息
-    let header = bold("❓ 问题已捕获\n\n");
+    let header = bold(&t(locale, &MessageId::AddQaCapturedHeader));
     let footer = FormattedText {
-        text: "\n\n管理员现在必须回复此消息以提供相应答案。".to_string(),
+        text: t(locale, &MessageId::AddQaFollowUp),
         entities: vec![],
     };
 
@@ -218,14 +309,17 @@ async fn handle_add_qa(
         .reply_markup(ui::simple_cancel_keyboard())
         .await?;
 
-    state.lock().await.pending_qas.insert(
-        (bot_message.chat.id, bot_message.id),
-        PendingQAInfo {
-            status: QAStatus::Answer {
+    state
+        .lock()
+        .await
+        .dialogue_store
+        .set(
+            (bot_message.chat.id, bot_message.id),
+            PendingQAInfo::new(QAStatus::Answer {
                 question: question_from_reply,
-            },
-        },
-    );
+            }),
+        )
+        .await?;
     Ok(())
 }
 
@@ -234,20 +328,22 @@ async fn handle_list_qa(
     message: Message,
     qa_service: Arc<Mutex<QAService>>,
     state: Arc<Mutex<AppState>>,
+    locale: Locale,
 ) -> Result<(), anyhow::Error> {
     let config = qa_service.lock().await.config.clone();
-    if !check_qa_ready(bot.clone(), message.chat.id, state, config).await? {
+    if !check_qa_ready(bot.clone(), message.chat.id, state, config, locale).await? {
         return Ok(());
     }
     let service_guard = qa_service.lock().await;
     let all_qas = service_guard.get_all_qa_items();
     if all_qas.is_empty() {
-        bot.send_message(message.chat.id, "未找到任何问答对。")
+        bot.send_message(message.chat.id, t(locale, &MessageId::ListQaEmpty))
             .await?;
         return Ok(());
     }
-    let keyboard = make_qa_keyboard(&all_qas);
-    bot.send_message(message.chat.id, "所有问答对。点击进行管理：")
+    let items = qa_list_items(&all_qas);
+    let keyboard = ui::qa_list_keyboard(&items, 0, QA_LIST_PAGE_SIZE);
+    bot.send_message(message.chat.id, t(locale, &MessageId::ListQaPrompt))
         .reply_markup(keyboard)
         .await?;
     Ok(())
@@ -259,13 +355,14 @@ async fn handle_search_qa(
     keywords: String,
     qa_service: Arc<Mutex<QAService>>,
     state: Arc<Mutex<AppState>>,
+    locale: Locale,
 ) -> Result<(), anyhow::Error> {
     let config = qa_service.lock().await.config.clone();
-    if !check_qa_ready(bot.clone(), message.chat.id, state, config).await? {
+    if !check_qa_ready(bot.clone(), message.chat.id, state, config, locale).await? {
         return Ok(());
     }
     if keywords.is_empty() {
-        bot.send_message(message.chat.id, "请输入要搜索的关键字。")
+        bot.send_message(message.chat.id, t(locale, &MessageId::SearchQaEmptyKeyword))
             .await?;
         return Ok(());
     }
@@ -276,12 +373,15 @@ async fn handle_search_qa(
     if matched_qas.is_empty() {
         bot.send_message(
             message.chat.id,
-            format!("未找到与“{}”相关的匹配项。", keywords),
+            t(
+                locale,
+                &MessageId::SearchQaNoMatches { keyword: keywords },
+            ),
         )
         .await?;
     } else {
         let keyboard = make_qa_keyboard(&matched_qas);
-        bot.send_message(message.chat.id, "找到以下问答对。点击进行管理：")
+        bot.send_message(message.chat.id, t(locale, &MessageId::SearchQaFound))
             .reply_markup(keyboard)
             .await?;
     }
@@ -294,6 +394,7 @@ async fn handle_snooze(
     minutes_str: String,
     state: Arc<Mutex<AppState>>,
     config: Arc<Config>,
+    locale: Locale,
 ) -> Result<(), anyhow::Error> {
     let mins = if minutes_str.is_empty() {
         60
@@ -304,7 +405,7 @@ async fn handle_snooze(
     let snoozed_until = Utc::now() + Duration::minutes(mins as i64);
     state.lock().await.snoozed_until = Some(snoozed_until);
     let sent = bot
-        .send_message(chat_id, format!("好的，我将暂停自动回复 {} 分钟。", mins))
+        .send_message(chat_id, t(locale, &MessageId::SnoozeAck { minutes: mins }))
         .await?;
     schedule_message_deletion(bot, config, sent);
     Ok(())
@@ -315,36 +416,119 @@ async fn handle_resume(
     chat_id: ChatId,
     state: Arc<Mutex<AppState>>,
     config: Arc<Config>,
+    locale: Locale,
 ) -> Result<(), anyhow::Error> {
     let mut state_guard = state.lock().await;
     if state_guard.snoozed_until.is_some() {
         state_guard.snoozed_until = None;
-        let sent = bot.send_message(chat_id, "好的，自动回复已恢复。").await?;
+        let sent = bot
+            .send_message(chat_id, t(locale, &MessageId::ResumeAck))
+            .await?;
         schedule_message_deletion(bot, config, sent);
     } else {
         let sent = bot
-            .send_message(chat_id, "我当前并未处于暂停状态。")
+            .send_message(chat_id, t(locale, &MessageId::ResumeNotSnoozed))
             .await?;
         schedule_message_deletion(bot, config, sent);
     }
     Ok(())
 }
 
+/// Resets `chat_id`'s multi-turn context buffer (see `AppState::conversation_context`),
+/// so a stale follow-up chain doesn't keep bleeding into unrelated questions.
+async fn handle_clear(
+    bot: Bot,
+    chat_id: ChatId,
+    state: Arc<Mutex<AppState>>,
+    locale: Locale,
+) -> Result<(), anyhow::Error> {
+    state.lock().await.clear_context(chat_id);
+    bot.send_message(chat_id, t(locale, &MessageId::ContextCleared))
+        .await?;
+    Ok(())
+}
+
+async fn handle_ingest(
+    bot: Bot,
+    message: Message,
+    source_name: String,
+    qa_service: Arc<Mutex<QAService>>,
+    locale: Locale,
+) -> Result<(), anyhow::Error> {
+    let replied_to = match message.reply_to_message() {
+        Some(m) => m,
+        None => {
+            bot.send_message(message.chat.id, t(locale, &MessageId::IngestNeedsReply))
+                .await?;
+            return Ok(());
+        }
+    };
+    let document_text = replied_to.text().unwrap_or_default();
+    if document_text.is_empty() {
+        bot.send_message(message.chat.id, t(locale, &MessageId::IngestNeedsText))
+            .await?;
+        return Ok(());
+    }
+    let source_name = if source_name.is_empty() {
+        "pasted-message".to_string()
+    } else {
+        source_name
+    };
+
+    let sent = bot
+        .send_message(message.chat.id, t(locale, &MessageId::IngestInProgress))
+        .await?;
+
+    let mut service_guard = qa_service.lock().await;
+    match service_guard.ingest_document(&source_name, document_text).await {
+        Ok(newly_embedded) => {
+            bot.edit_message_text(
+                sent.chat.id,
+                sent.id,
+                t(
+                    locale,
+                    &MessageId::IngestDone {
+                        source: source_name,
+                        count: newly_embedded,
+                    },
+                ),
+            )
+            .await?;
+        }
+        Err(e) => {
+            log::error!("Failed to ingest document '{}': {:?}", source_name, e);
+            bot.edit_message_text(
+                sent.chat.id,
+                sent.id,
+                t(
+                    locale,
+                    &MessageId::IngestFailed {
+                        error: e.to_string(),
+                    },
+                ),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
 async fn handle_answer(
     bot: Bot,
     message: Message,
     qa_service: Arc<Mutex<QAService>>,
     state: Arc<Mutex<AppState>>,
+    locale: Locale,
 ) -> Result<(), anyhow::Error> {
     let config = qa_service.lock().await.config.clone();
-    if !check_qa_ready(bot.clone(), message.chat.id, state, config.clone()).await? {
+    if !check_qa_ready(bot.clone(), message.chat.id, state, config.clone(), locale).await? {
         return Ok(());
     }
     let replied_to = match message.reply_to_message() {
         Some(m) => m,
         None => {
             let sent = bot
-                .send_message(message.chat.id, "请通过回复您想提问的消息来使用此命令。")
+                .send_message(message.chat.id, t(locale, &MessageId::AnswerNeedsReply))
                 .await?;
             schedule_message_deletion(bot, config, sent);
             return Ok(());
@@ -353,7 +537,7 @@ async fn handle_answer(
     let question_text = replied_to.text().unwrap_or_default();
     if question_text.is_empty() {
         let sent = bot
-            .send_message(message.chat.id, "被回复的消息必须包含文本。")
+            .send_message(message.chat.id, t(locale, &MessageId::AnswerNeedsText))
             .await?;
         schedule_message_deletion(bot, config, sent);
         return Ok(());
@@ -374,7 +558,7 @@ async fn handle_answer(
         }
         Ok(None) => {
             let sent = bot
-                .send_message(replied_to.chat.id, "抱歉，我找不到该问题的答案。")
+                .send_message(replied_to.chat.id, t(locale, &MessageId::AnswerNotFound))
                 .reply_to(replied_to.id)
                 .await?;
             schedule_message_deletion(bot, config, sent);
@@ -382,7 +566,7 @@ async fn handle_answer(
         Err(e) => {
             log::error!("Error finding matching QA: {:?}", e);
             let sent = bot
-                .send_message(replied_to.chat.id, "搜索答案时发生错误。")
+                .send_message(replied_to.chat.id, t(locale, &MessageId::AnswerSearchError))
                 .reply_to(replied_to.id)
                 .await?;
             schedule_message_deletion(bot, config, sent);
@@ -390,3 +574,157 @@ async fn handle_answer(
     }
     Ok(())
 }
+
+async fn handle_lang(
+    bot: Bot,
+    chat_id: ChatId,
+    code: String,
+    state: Arc<Mutex<AppState>>,
+    current_locale: Locale,
+) -> Result<(), anyhow::Error> {
+    if code.is_empty() {
+        bot.send_message(chat_id, t(current_locale, &MessageId::LangUsage))
+            .await?;
+        return Ok(());
+    }
+    match Locale::parse(&code) {
+        Some(new_locale) => {
+            state.lock().await.chat_locales.insert(chat_id, new_locale);
+            bot.send_message(
+                chat_id,
+                t(new_locale, &MessageId::LangSet { locale: new_locale }),
+            )
+            .await?;
+        }
+        None => {
+            bot.send_message(
+                chat_id,
+                t(current_locale, &MessageId::LangUnknown { code }),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_stats(
+    bot: Bot,
+    chat_id: ChatId,
+    qa_service: Arc<Mutex<QAService>>,
+    state: Arc<Mutex<AppState>>,
+    locale: Locale,
+) -> Result<(), anyhow::Error> {
+    let service_guard = qa_service.lock().await;
+    let qa_count = service_guard.get_all_qa_items().len();
+    let (healthy_keys, cooling_down_keys, quarantined_keys) = service_guard.key_health();
+    let config = service_guard.config.clone();
+    drop(service_guard);
+
+    let state_guard = state.lock().await;
+    let snoozed = state_guard
+        .snoozed_until
+        .is_some_and(|until| chrono::Utc::now() < until);
+    let hits = state_guard.auto_reply_hits;
+    let misses = state_guard.auto_reply_misses;
+    drop(state_guard);
+
+    bot.send_message(
+        chat_id,
+        t(
+            locale,
+            &MessageId::Stats {
+                qa_count,
+                embedding_model: config.embedding.model.clone(),
+                embedding_ndims: config.embedding.ndims,
+                healthy_keys,
+                cooling_down_keys,
+                quarantined_keys,
+                snoozed,
+                hits,
+                misses,
+            },
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Forces an immediate reload of the config file and QA data, rather than
+/// waiting for `qa::watcher`'s next poll — useful right after an operator
+/// edits either file and wants the change to take effect without delay.
+async fn handle_reload(
+    bot: Bot,
+    chat_id: ChatId,
+    qa_service: Arc<Mutex<QAService>>,
+    locale: Locale,
+) -> Result<(), anyhow::Error> {
+    match config::load_user_config() {
+        Ok(new_config) => qa_service.lock().await.reload_config(new_config),
+        Err(e) => {
+            bot.send_message(
+                chat_id,
+                t(
+                    locale,
+                    &MessageId::ReloadFailed {
+                        error: e.to_string(),
+                    },
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    }
+
+    match qa_service.lock().await.reload_qa_data().await {
+        Ok(summary) => {
+            bot.send_message(
+                chat_id,
+                t(
+                    locale,
+                    &MessageId::ReloadDone {
+                        added: summary.added,
+                        updated: summary.updated,
+                        removed: summary.removed,
+                        unchanged: summary.unchanged,
+                    },
+                ),
+            )
+            .await?;
+        }
+        Err(e) => {
+            bot.send_message(
+                chat_id,
+                t(
+                    locale,
+                    &MessageId::ReloadFailed {
+                        error: e.to_string(),
+                    },
+                ),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_pardon(
+    bot: Bot,
+    message: Message,
+    state: Arc<Mutex<AppState>>,
+    locale: Locale,
+) -> Result<(), anyhow::Error> {
+    let Some(target) = message.reply_to_message().and_then(|m| m.from.as_ref()) else {
+        bot.send_message(message.chat.id, t(locale, &MessageId::PardonNeedsReply))
+            .await?;
+        return Ok(());
+    };
+
+    if moderation::pardon(&bot, message.chat.id, target.id, &state).await? {
+        bot.send_message(message.chat.id, t(locale, &MessageId::PardonAck))
+            .await?;
+    } else {
+        bot.send_message(message.chat.id, t(locale, &MessageId::PardonNotMuted))
+            .await?;
+    }
+    Ok(())
+}
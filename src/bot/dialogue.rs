@@ -0,0 +1,401 @@
+//! src/bot/dialogue.rs
+//!
+//! A first-class state machine for the interactive add/edit-QA flow. Before
+//! this module existed, `QAStatus` transitions were scattered inline across
+//! `handle_qa_reply` and `callback_handler`; now [`advance`] is the single
+//! pure transition function both of them drive, so the flow is testable in
+//! isolation from any live bot. Conversations live in a pluggable
+//! [`DialogueStore`] instead of directly on `AppState`, so an on-disk backend
+//! can make them survive a restart, and each one expires after
+//! `config.dialogue.ttl_secs` of inactivity (see [`DialogueStore::prune_expired`]).
+
+use crate::config::{Config, DialogueBackend};
+use crate::qa::types::FormattedText;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use teloxide::types::{ChatId, MessageId};
+use tokio::sync::{Mutex, OnceCell};
+
+/// Identifies a conversation: the chat and the bot's prompt message that the
+/// admin is expected to reply to.
+pub type DialogueKey = (ChatId, MessageId);
+
+/// Where a single add/edit-QA conversation currently stands.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum QAStatus {
+    /// The bot is waiting for an administrator to reply with an answer.
+    Answer { question: FormattedText },
+    /// The bot has received an answer and is waiting for confirmation.
+    Confirmation {
+        question: FormattedText,
+        answer: FormattedText,
+    },
+    /// Waiting for an admin to reply with the new question text.
+    EditQuestion {
+        old_question_hash: String,
+        original_answer: FormattedText,
+    },
+    /// Waiting for an admin to reply with the new answer text.
+    EditAnswer {
+        old_question_hash: String,
+        original_question: FormattedText,
+    },
+}
+
+/// A conversation plus when it entered its current state (each transition
+/// stores a fresh `PendingQAInfo`), so [`DialogueStore::prune_expired`] can
+/// find ones that have gone stale.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingQAInfo {
+    pub status: QAStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PendingQAInfo {
+    pub fn new(status: QAStatus) -> Self {
+        Self {
+            status,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// An input driving a transition via [`advance`].
+pub enum DialogueInput {
+    /// The admin replied with new text (an answer, a corrected question, ...).
+    Reply(FormattedText),
+    Confirm,
+    Reedit,
+    Cancel,
+}
+
+/// A side effect [`advance`] asks the caller to perform. Kept separate from
+/// the state transition itself so the FSM logic has no I/O and can be unit
+/// tested as plain data in, data out.
+pub enum DialogueAction {
+    /// Re-render the prompt for the conversation's (new) current state.
+    Show,
+    /// Persist `question`/`answer` as a brand-new QA item.
+    SaveNew {
+        question: FormattedText,
+        answer: FormattedText,
+    },
+    /// Persist an edit to the existing item keyed by `old_question_hash`.
+    SaveEdit {
+        old_question_hash: String,
+        question: FormattedText,
+        answer: FormattedText,
+    },
+    /// The conversation is over; remove it from the store.
+    End,
+}
+
+/// The single transition function driving the add/edit-QA flow. Given the
+/// conversation's current status and an input, returns the next status
+/// (`None` if the conversation has ended) plus the actions the caller should
+/// take in response.
+pub fn advance(
+    status: &QAStatus,
+    input: DialogueInput,
+) -> (Option<QAStatus>, Vec<DialogueAction>) {
+    match (status, input) {
+        (QAStatus::Answer { question }, DialogueInput::Reply(answer)) => (
+            Some(QAStatus::Confirmation {
+                question: question.clone(),
+                answer,
+            }),
+            vec![DialogueAction::Show],
+        ),
+        (QAStatus::Confirmation { question, .. }, DialogueInput::Reedit) => (
+            Some(QAStatus::Answer {
+                question: question.clone(),
+            }),
+            vec![DialogueAction::Show],
+        ),
+        (QAStatus::Confirmation { question, answer }, DialogueInput::Confirm) => (
+            None,
+            vec![
+                DialogueAction::SaveNew {
+                    question: question.clone(),
+                    answer: answer.clone(),
+                },
+                DialogueAction::End,
+            ],
+        ),
+        (
+            QAStatus::EditQuestion {
+                old_question_hash,
+                original_answer,
+            },
+            DialogueInput::Reply(new_question),
+        ) => (
+            None,
+            vec![
+                DialogueAction::SaveEdit {
+                    old_question_hash: old_question_hash.clone(),
+                    question: new_question,
+                    answer: original_answer.clone(),
+                },
+                DialogueAction::End,
+            ],
+        ),
+        (
+            QAStatus::EditAnswer {
+                old_question_hash,
+                original_question,
+            },
+            DialogueInput::Reply(new_answer),
+        ) => (
+            None,
+            vec![
+                DialogueAction::SaveEdit {
+                    old_question_hash: old_question_hash.clone(),
+                    question: original_question.clone(),
+                    answer: new_answer,
+                },
+                DialogueAction::End,
+            ],
+        ),
+        (_, DialogueInput::Cancel) => (None, vec![DialogueAction::End]),
+        // Any input that doesn't apply to the current state leaves it
+        // unchanged and triggers no side effect.
+        (current, _) => (Some(current.clone()), vec![]),
+    }
+}
+
+/// Storage backend for in-progress conversations, so they can optionally
+/// survive a process restart instead of living only in `AppState`.
+#[async_trait]
+pub trait DialogueStore: Send + Sync {
+    async fn get(&self, key: &DialogueKey) -> Result<Option<PendingQAInfo>>;
+    async fn set(&self, key: DialogueKey, info: PendingQAInfo) -> Result<()>;
+    async fn remove(&self, key: &DialogueKey) -> Result<()>;
+    /// Drops every conversation older than `ttl_secs`.
+    async fn prune_expired(&self, ttl_secs: u64) -> Result<()>;
+}
+
+/// Builds the [`DialogueStore`] selected by `config.dialogue.backend`.
+pub fn build_dialogue_store(config: &Config) -> Box<dyn DialogueStore> {
+    match config.dialogue.backend {
+        DialogueBackend::InMemory => Box::new(InMemoryDialogueStore::new()),
+        DialogueBackend::JsonFile => {
+            Box::new(JsonFileDialogueStore::new(config.dialogue.json_path.clone()))
+        }
+        DialogueBackend::Redis => Box::new(RedisDialogueStore::new(
+            config.dialogue.redis_url.clone(),
+            config.dialogue.redis_key_prefix.clone(),
+            config.dialogue.ttl_secs,
+        )),
+    }
+}
+
+fn prune_map(map: &mut HashMap<DialogueKey, PendingQAInfo>, ttl_secs: u64) {
+    let cutoff = Utc::now() - chrono::Duration::seconds(ttl_secs as i64);
+    map.retain(|_, info| info.created_at > cutoff);
+}
+
+/// Default backend: conversations live only for the life of the process.
+pub struct InMemoryDialogueStore {
+    conversations: Mutex<HashMap<DialogueKey, PendingQAInfo>>,
+}
+
+impl InMemoryDialogueStore {
+    pub fn new() -> Self {
+        Self {
+            conversations: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryDialogueStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DialogueStore for InMemoryDialogueStore {
+    async fn get(&self, key: &DialogueKey) -> Result<Option<PendingQAInfo>> {
+        Ok(self.conversations.lock().await.get(key).cloned())
+    }
+
+    async fn set(&self, key: DialogueKey, info: PendingQAInfo) -> Result<()> {
+        self.conversations.lock().await.insert(key, info);
+        Ok(())
+    }
+
+    async fn remove(&self, key: &DialogueKey) -> Result<()> {
+        self.conversations.lock().await.remove(key);
+        Ok(())
+    }
+
+    async fn prune_expired(&self, ttl_secs: u64) -> Result<()> {
+        prune_map(&mut self.conversations.lock().await, ttl_secs);
+        Ok(())
+    }
+}
+
+/// On-disk JSON shape for a single conversation entry. `DialogueKey` (a
+/// `(ChatId, MessageId)` tuple) can't be a `serde_json` map key directly, so
+/// it's flattened into a list of entries instead.
+#[derive(Serialize, Deserialize)]
+struct StoredEntry {
+    chat_id: i64,
+    message_id: i32,
+    info: PendingQAInfo,
+}
+
+fn key_of(entry: &StoredEntry) -> DialogueKey {
+    (ChatId(entry.chat_id), MessageId(entry.message_id))
+}
+
+/// On-disk backend so conversations survive a bot restart: the whole
+/// conversation map is kept in memory and rewritten to `path` as JSON after
+/// every mutation, mirroring how `qa::persistence` stores the QA corpus.
+pub struct JsonFileDialogueStore {
+    path: PathBuf,
+    conversations: Mutex<HashMap<DialogueKey, PendingQAInfo>>,
+}
+
+impl JsonFileDialogueStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let conversations = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Vec<StoredEntry>>(&contents).ok())
+            .map(|entries| {
+                entries
+                    .into_iter()
+                    .map(|entry| (key_of(&entry), entry.info))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            path,
+            conversations: Mutex::new(conversations),
+        }
+    }
+
+    async fn persist(&self, conversations: &HashMap<DialogueKey, PendingQAInfo>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let entries: Vec<StoredEntry> = conversations
+            .iter()
+            .map(|((chat_id, message_id), info)| StoredEntry {
+                chat_id: chat_id.0,
+                message_id: message_id.0,
+                info: info.clone(),
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&entries)?;
+        tokio::fs::write(&self.path, json).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DialogueStore for JsonFileDialogueStore {
+    async fn get(&self, key: &DialogueKey) -> Result<Option<PendingQAInfo>> {
+        Ok(self.conversations.lock().await.get(key).cloned())
+    }
+
+    async fn set(&self, key: DialogueKey, info: PendingQAInfo) -> Result<()> {
+        let mut conversations = self.conversations.lock().await;
+        conversations.insert(key, info);
+        self.persist(&conversations).await
+    }
+
+    async fn remove(&self, key: &DialogueKey) -> Result<()> {
+        let mut conversations = self.conversations.lock().await;
+        conversations.remove(key);
+        self.persist(&conversations).await
+    }
+
+    async fn prune_expired(&self, ttl_secs: u64) -> Result<()> {
+        let mut conversations = self.conversations.lock().await;
+        prune_map(&mut conversations, ttl_secs);
+        self.persist(&conversations).await
+    }
+}
+
+/// Redis-backed store so conversations are shared across bot replicas
+/// instead of each instance keeping its own, mirroring how `qa::store::Store`
+/// lets multiple replicas share one QA corpus. Each conversation is written
+/// with `ttl_secs` as a native Redis key expiry, so [`prune_expired`] has
+/// nothing to do — expired entries simply aren't there anymore.
+///
+/// [`prune_expired`]: DialogueStore::prune_expired
+pub struct RedisDialogueStore {
+    url: String,
+    key_prefix: String,
+    ttl_secs: u64,
+    client: OnceCell<redis::Client>,
+}
+
+impl RedisDialogueStore {
+    pub fn new(url: String, key_prefix: String, ttl_secs: u64) -> Self {
+        Self {
+            url,
+            key_prefix,
+            ttl_secs,
+            client: OnceCell::new(),
+        }
+    }
+
+    async fn connection(&self) -> Result<redis::aio::MultiplexedConnection> {
+        let client = self
+            .client
+            .get_or_try_init(|| async { redis::Client::open(self.url.clone()) })
+            .await
+            .context("Failed to create Redis client")?;
+        client
+            .get_multiplexed_async_connection()
+            .await
+            .context("Failed to connect to Redis")
+    }
+
+    fn redis_key(&self, key: &DialogueKey) -> String {
+        format!("{}:{}:{}", self.key_prefix, key.0.0, key.1.0)
+    }
+}
+
+#[async_trait]
+impl DialogueStore for RedisDialogueStore {
+    async fn get(&self, key: &DialogueKey) -> Result<Option<PendingQAInfo>> {
+        let mut conn = self.connection().await?;
+        let raw: Option<String> = conn
+            .get(self.redis_key(key))
+            .await
+            .context("Failed to read conversation from Redis")?;
+        raw.map(|json| {
+            serde_json::from_str(&json).context("Failed to deserialize conversation from Redis")
+        })
+        .transpose()
+    }
+
+    async fn set(&self, key: DialogueKey, info: PendingQAInfo) -> Result<()> {
+        let mut conn = self.connection().await?;
+        let json = serde_json::to_string(&info).context("Failed to serialize conversation")?;
+        conn.set_ex::<_, _, ()>(self.redis_key(&key), json, self.ttl_secs)
+            .await
+            .context("Failed to write conversation to Redis")
+    }
+
+    async fn remove(&self, key: &DialogueKey) -> Result<()> {
+        let mut conn = self.connection().await?;
+        conn.del::<_, ()>(self.redis_key(key))
+            .await
+            .context("Failed to delete conversation from Redis")
+    }
+
+    async fn prune_expired(&self, _ttl_secs: u64) -> Result<()> {
+        // Redis expires each key on its own via `set_ex` at write time.
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+//! src/bot/hooks.rs
+//!
+//! A reusable pre/post hook layer that runs ahead of `commands::command_handler`,
+//! `callbacks::callback_handler`, and `message::message_handler`. Cross-cutting
+//! concerns that used to be re-checked ad hoc inside each handler (the group
+//! allowlist, the private-chat super-admin gate) now live here as `Hook`s run
+//! uniformly by a shared [`HookRegistry`].
+
+use crate::bot::utils::is_super_admin;
+use crate::config::Config;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use teloxide::types::{ChatId, UserId};
+use tokio::sync::Mutex;
+
+/// Which handler an update is about to be dispatched to, for hooks that want
+/// to distinguish commands from callbacks or generic messages (e.g. logging).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateKind {
+    Command,
+    Callback,
+    Message,
+}
+
+/// Everything a [`Hook`] needs to decide whether an update may proceed.
+pub struct HookContext {
+    pub kind: UpdateKind,
+    pub chat_id: ChatId,
+    pub user_id: Option<UserId>,
+    pub is_private: bool,
+    pub config: Arc<Config>,
+}
+
+/// What a hook decided about the update it was shown.
+#[derive(Debug, Clone)]
+pub enum HookOutcome {
+    /// Allow the update to continue to the next hook, then the handler.
+    Continue,
+    /// Stop processing here. `reason` is for logs; handlers decide for
+    /// themselves whether (and what) to reply to the user, since the right
+    /// reply differs by handler (e.g. a command gets an explanation, a
+    /// generic message is just dropped).
+    Reject { reason: String },
+}
+
+/// A cross-cutting check (or side effect) that runs ahead of a handler. Both
+/// `pre` and `post` run before the handler's own work; no hook runs after
+/// the handler has finished.
+#[async_trait]
+pub trait Hook: Send + Sync {
+    /// Runs before the handler. Returning `Reject` stops the chain.
+    async fn pre(&self, ctx: &HookContext) -> HookOutcome;
+
+    /// Runs after every hook's `pre` has allowed the update through.
+    /// Default is a no-op.
+    async fn post(&self, _ctx: &HookContext) {}
+}
+
+/// Runs a fixed, ordered list of [`Hook`]s ahead of a handler, short-circuiting
+/// on the first rejection.
+#[derive(Clone)]
+pub struct HookRegistry {
+    hooks: Arc<Vec<Arc<dyn Hook>>>,
+}
+
+impl HookRegistry {
+    pub fn new(hooks: Vec<Arc<dyn Hook>>) -> Self {
+        Self {
+            hooks: Arc::new(hooks),
+        }
+    }
+
+    /// Runs every hook's `pre` in order, returning the first `Reject`.
+    pub async fn run_pre(&self, ctx: &HookContext) -> HookOutcome {
+        for hook in self.hooks.iter() {
+            if let HookOutcome::Reject { reason } = hook.pre(ctx).await {
+                return HookOutcome::Reject { reason };
+            }
+        }
+        HookOutcome::Continue
+    }
+
+    /// Runs every hook's `post`, in order. Only meant to be called once
+    /// `run_pre` returned `Continue`.
+    pub async fn run_post(&self, ctx: &HookContext) {
+        for hook in self.hooks.iter() {
+            hook.post(ctx).await;
+        }
+    }
+}
+
+/// Rejects updates from groups not on `config.telegram.allowed_group_ids`,
+/// and private-chat updates from users who aren't a configured super admin.
+/// Replaces the equivalent checks that used to be duplicated at the top of
+/// `command_handler` and inside `message::handle_generic_message`.
+///
+/// Deliberately does *not* replace `callback_handler`'s own `is_admin` check:
+/// that one requires the clicking user to be an admin (super admin or, in a
+/// group, a Telegram chat administrator) to act on a QA management button,
+/// which is a stricter, action-specific authorization rather than this
+/// coarse "is this chat allowed at all" gate.
+pub struct AccessGateHook;
+
+#[async_trait]
+impl Hook for AccessGateHook {
+    async fn pre(&self, ctx: &HookContext) -> HookOutcome {
+        if ctx.is_private {
+            return match ctx.user_id {
+                Some(uid) if is_super_admin(uid, &ctx.config) => HookOutcome::Continue,
+                _ => HookOutcome::Reject {
+                    reason: format!("private chat {} from non-super-admin", ctx.chat_id),
+                },
+            };
+        }
+
+        let allowed = &ctx.config.telegram.allowed_group_ids;
+        if !allowed.is_empty() && !allowed.contains(&ctx.chat_id.0) {
+            return HookOutcome::Reject {
+                reason: format!("unauthorized group {}", ctx.chat_id),
+            };
+        }
+        HookOutcome::Continue
+    }
+}
+
+/// Logs a uniform one-line summary of every update that reaches a handler,
+/// so operators have a consistent audit trail instead of each handler
+/// logging its own ad hoc message (or none at all).
+pub struct RequestLoggingHook;
+
+#[async_trait]
+impl Hook for RequestLoggingHook {
+    async fn pre(&self, ctx: &HookContext) -> HookOutcome {
+        log::info!(
+            "update kind={:?} chat={} user={:?}",
+            ctx.kind,
+            ctx.chat_id,
+            ctx.user_id
+        );
+        HookOutcome::Continue
+    }
+}
+
+/// Rejects updates once a `(chat, user)` pair exceeds `max_requests` within
+/// a sliding window, to blunt runaway command/message loops before they
+/// reach Gemini or the store. Configured by `config.rate_limit`.
+pub struct RateLimitHook {
+    max_requests: usize,
+    window: chrono::Duration,
+    history: Mutex<HashMap<(ChatId, UserId), VecDeque<DateTime<Utc>>>>,
+}
+
+impl RateLimitHook {
+    pub fn new(max_requests: u32, window_secs: u64) -> Self {
+        Self {
+            max_requests: max_requests as usize,
+            window: chrono::Duration::seconds(window_secs as i64),
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Hook for RateLimitHook {
+    async fn pre(&self, ctx: &HookContext) -> HookOutcome {
+        let Some(user_id) = ctx.user_id else {
+            return HookOutcome::Continue;
+        };
+        let key = (ctx.chat_id, user_id);
+        let now = Utc::now();
+
+        let mut history = self.history.lock().await;
+        let timestamps = history.entry(key).or_default();
+        while let Some(&oldest) = timestamps.front() {
+            if now - oldest > self.window {
+                timestamps.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if timestamps.len() >= self.max_requests {
+            return HookOutcome::Reject {
+                reason: format!(
+                    "rate limit exceeded for user {} in chat {}",
+                    user_id, ctx.chat_id
+                ),
+            };
+        }
+
+        timestamps.push_back(now);
+        HookOutcome::Continue
+    }
+}
@@ -1,4 +1,7 @@
-use crate::bot::state::{AppState, QAStatus};
+use crate::bot::dialogue::{self, DialogueAction, DialogueInput, QAStatus};
+use crate::bot::hooks::{HookContext, HookOutcome, HookRegistry, UpdateKind};
+use crate::bot::moderation;
+use crate::bot::state::AppState;
 use crate::bot::ui;
 use crate::bot::utils::{
     bold, combine_texts, ensure_blockquote, is_admin, schedule_message_deletion,
@@ -17,23 +20,27 @@ pub async fn message_handler(
     message: Message,
     qa_service: Arc<Mutex<QAService>>,
     state: Arc<Mutex<AppState>>,
+    hook_registry: Arc<HookRegistry>,
 ) -> Result<(), anyhow::Error> {
     // Clone config from service at the beginning
     let config = qa_service.lock().await.config.clone();
 
-    if (message.chat.is_group() || message.chat.is_supergroup())
-        && !config.telegram.allowed_group_ids.is_empty()
-        && !config
-            .telegram
-            .allowed_group_ids
-            .contains(&message.chat.id.0)
-    {
-        log::warn!(
-            "Ignoring message from unauthorized group: {}",
-            message.chat.id
+    let hook_ctx = HookContext {
+        kind: UpdateKind::Message,
+        chat_id: message.chat.id,
+        user_id: message.from.as_ref().map(|u| u.id),
+        is_private: message.chat.is_private(),
+        config: config.clone(),
+    };
+    if let HookOutcome::Reject { reason } = hook_registry.run_pre(&hook_ctx).await {
+        log::info!(
+            "Hook rejected message in chat {}: {}",
+            message.chat.id,
+            reason
         );
         return Ok(());
     }
+    hook_registry.run_post(&hook_ctx).await;
 
     // First, try to handle it as a reply in a QA flow.
     let handled_as_reply = handle_qa_reply(
@@ -76,98 +83,99 @@ async fn handle_qa_reply(
     };
 
     let pending_qa_key = (message.chat.id, reply_to.id);
-    let mut state_guard = state.lock().await;
+    // Must clone config here to release the lock on qa_service quickly.
+    let config_clone = qa_service.lock().await.config.clone();
+    let locale = state
+        .lock()
+        .await
+        .locale_for(message.chat.id, config_clone.i18n.default_locale);
 
-    if let Some(pending_qa) = state_guard.pending_qas.get_mut(&pending_qa_key) {
-        // Must clone config here to release the lock on qa_service quickly
-        let config_clone = qa_service.lock().await.config.clone();
-        if !is_admin(&bot, message.chat.id, user.id, &config_clone).await {
-            return Ok(true);
-        }
+    let Some(pending_qa) = state.lock().await.dialogue_store.get(&pending_qa_key).await? else {
+        return Ok(false);
+    };
 
-        let new_formatted_text = FormattedText {
-            text: new_text,
-            entities: new_entities,
-        };
+    if !is_admin(&bot, message.chat.id, user.id, &config_clone).await {
+        return Ok(true);
+    }
 
-        let current_status = pending_qa.status.clone();
-        match current_status {
-            QAStatus::Answer { question } => {
-                pending_qa.status = QAStatus::Confirmation {
-                    question: question.clone(),
-                    answer: new_formatted_text.clone(),
-                };
+    let new_formatted_text = FormattedText {
+        text: new_text,
+        entities: new_entities,
+    };
 
-                let display_question =
-                    ensure_blockquote(question.clone(), MessageEntityKind::ExpandableBlockquote);
-                let display_answer = ensure_blockquote(
-                    new_formatted_text.clone(),
-                    MessageEntityKind::ExpandableBlockquote,
-                );
+    let (next_status, actions) =
+        dialogue::advance(&pending_qa.status, DialogueInput::Reply(new_formatted_text));
 
-                // 创建带格式的各个部分
-                let title = bold("Is this Q&A pair correct?");
-                let q_header = bold("\n\nQ:\n");
-                let a_header = bold("\n\nA:\n");
+    for action in actions {
+        match action {
+            DialogueAction::Show => {
+                if let Some(QAStatus::Confirmation { question, answer }) = &next_status {
+                    let display_question = ensure_blockquote(
+                        question.clone(),
+                        MessageEntityKind::ExpandableBlockquote,
+                    );
+                    let display_answer =
+                        ensure_blockquote(answer.clone(), MessageEntityKind::ExpandableBlockquote);
 
-                // 使用新的 combine_texts 函数将它们组合起来
-                let combined = combine_texts(&[
-                    &title,
-                    &q_header,
-                    &display_question,
-                    &a_header,
-                    &display_answer,
-                ]);
+                    // 创建带格式的各个部分
+                    let title = bold("Is this Q&A pair correct?");
+                    let q_header = bold("\n\nQ:\n");
+                    let a_header = bold("\n\nA:\n");
 
-                bot.edit_message_text(pending_qa_key.0, pending_qa_key.1, combined.text)
-                    .entities(combined.entities)
-                    .reply_markup(ui::confirm_reedit_cancel_keyboard())
-                    .await?;
-            }
-            QAStatus::EditQuestion {
-                old_question_hash,
-                original_answer,
-            } => {
-                drop(state_guard);
-                let mut service_guard = qa_service.lock().await;
-                service_guard
-                    .update_qa(&old_question_hash, &new_formatted_text, &original_answer)
-                    .await?;
-                bot.edit_message_text(
-                    pending_qa_key.0,
-                    pending_qa_key.1,
-                    "✅ QA pair updated successfully!",
-                )
-                .await?;
-                state.lock().await.pending_qas.remove(&pending_qa_key);
+                    // 使用新的 combine_texts 函数将它们组合起来
+                    let combined = combine_texts(&[
+                        &title,
+                        &q_header,
+                        &display_question,
+                        &a_header,
+                        &display_answer,
+                    ]);
+
+                    bot.edit_message_text(pending_qa_key.0, pending_qa_key.1, combined.text)
+                        .entities(combined.entities)
+                        .reply_markup(ui::confirm_reedit_cancel_keyboard())
+                        .await?;
+                }
             }
-            QAStatus::EditAnswer {
+            DialogueAction::SaveEdit {
                 old_question_hash,
-                original_question,
+                question,
+                answer,
             } => {
-                drop(state_guard);
-                let mut service_guard = qa_service.lock().await;
-                service_guard
-                    .update_qa(&old_question_hash, &original_question, &new_formatted_text)
+                qa_service
+                    .lock()
+                    .await
+                    .update_qa(&old_question_hash, &question, &answer)
                     .await?;
                 bot.edit_message_text(
                     pending_qa_key.0,
                     pending_qa_key.1,
-                    "✅ QA pair updated successfully!",
+                    crate::i18n::t(locale, &crate::i18n::MessageId::QaUpdated),
                 )
                 .await?;
-                state.lock().await.pending_qas.remove(&pending_qa_key);
             }
-            _ => {}
+            DialogueAction::SaveNew { .. } | DialogueAction::End => {}
         }
+    }
 
-        if let Err(e) = bot.delete_message(message.chat.id, message.id).await {
-            log::warn!("Failed to delete admin's reply message: {:?}", e);
+    match next_status {
+        Some(status) => {
+            state
+                .lock()
+                .await
+                .dialogue_store
+                .set(pending_qa_key, dialogue::PendingQAInfo::new(status))
+                .await?;
         }
-        Ok(true)
-    } else {
-        Ok(false)
+        None => {
+            state.lock().await.dialogue_store.remove(&pending_qa_key).await?;
+        }
+    }
+
+    if let Err(e) = bot.delete_message(message.chat.id, message.id).await {
+        log::warn!("Failed to delete admin's reply message: {:?}", e);
     }
+    Ok(true)
 }
 
 /// Handles any message that is not a command or a reply in a QA flow.
@@ -199,22 +207,33 @@ pub async fn handle_generic_message(
         }
     }
 
-    if message.chat.is_private() {
-        if let Some(ref user) = message.from {
-            if !crate::bot::utils::is_super_admin(user.id, &config) {
-                return Ok(());
-            }
-        } else {
-            return Ok(());
-        }
-    }
-
     let current_time = chrono::Utc::now().timestamp();
     if (current_time - message.date.timestamp()) > config.message.timeout {
         return Ok(());
     }
 
-    if let Some(text) = message.text() {
+    let locale = state
+        .lock()
+        .await
+        .locale_for(message.chat.id, config.i18n.default_locale);
+    if moderation::enforce(&bot, &message, &config, &state, locale).await? {
+        return Ok(());
+    }
+
+    let recognized_text = match crate::ocr::extract_text(&bot, &message, &config).await {
+        Ok(text) => text,
+        Err(e) => {
+            log::warn!(
+                "OCR failed for message {} in chat {}: {:?}",
+                message.id,
+                message.chat.id,
+                e
+            );
+            None
+        }
+    };
+
+    if let Some(text) = recognized_text {
         if message.chat.is_group() || message.chat.is_supergroup() {
             schedule_message_deletion(bot.clone(), config.clone(), message.clone());
         }
@@ -224,13 +243,46 @@ pub async fn handle_generic_message(
             message.chat.id,
             text
         );
+
+        // Multi-turn context: fold the last `context_turns` user messages
+        // into a single query so a follow-up like "and what about cargo?"
+        // carries enough signal on its own. Reusing that combined text for
+        // the embedding also reranks the retrieved candidates against the
+        // full context, with no extra embedding call needed. A single turn
+        // (the default) is equivalent to today's standalone-message query.
+        let query_text = if config.message.context_turns > 1 {
+            let mut state_guard = state.lock().await;
+            state_guard.push_context_turn(
+                message.chat.id,
+                text.clone(),
+                config.message.context_turns,
+            );
+            state_guard
+                .context_query(message.chat.id, config.message.context_ttl_secs)
+                .unwrap_or_else(|| text.clone())
+        } else {
+            text.clone()
+        };
+
         let service_guard = qa_service.lock().await;
 
-        match service_guard.find_matching_qa(text).await {
-            Ok(Some(qa_item)) => {
-                log::info!("Found matching QA: {:?}", qa_item);
-                let answer =
-                    ensure_blockquote(qa_item.answer, MessageEntityKind::ExpandableBlockquote);
+        let matched_answer = if config.generation.enabled {
+            service_guard
+                .find_matching_qa_generative(&query_text)
+                .await
+                .map(|found| found.map(|answer| answer.text))
+        } else {
+            service_guard
+                .find_matching_qa(&query_text)
+                .await
+                .map(|found| found.map(|item| item.answer))
+        };
+
+        match matched_answer {
+            Ok(Some(answer_text)) => {
+                state.lock().await.auto_reply_hits += 1;
+                log::info!("Found matching answer for: {}", text);
+                let answer = ensure_blockquote(answer_text, MessageEntityKind::ExpandableBlockquote);
 
                 let sent_message = bot
                     .send_message(message.chat.id, answer.text)
@@ -246,9 +298,11 @@ pub async fn handle_generic_message(
                 schedule_message_deletion(bot, config, sent_message);
             }
             Ok(None) => {
+                state.lock().await.auto_reply_misses += 1;
                 log::info!("No match found for: {}", text);
             }
             Err(e) => {
+                state.lock().await.embedding_errors += 1;
                 log::error!("Error finding matching QA: {:?}", e);
             }
         }
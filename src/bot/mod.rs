@@ -0,0 +1,10 @@
+pub mod callbacks;
+pub mod commands;
+mod dialogue;
+pub mod hooks;
+pub mod message;
+mod moderation;
+pub mod state;
+mod types;
+mod ui;
+mod utils;
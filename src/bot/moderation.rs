@@ -0,0 +1,132 @@
+//! src/bot/moderation.rs
+//!
+//! Lightweight anti-spam throttle for group chats, invoked from
+//! `handle_generic_message` before a message reaches the embedding lookup.
+//! Tracks recent message timestamps per `(chat, user)` in
+//! `AppState::message_activity` and, once a non-admin sender exceeds
+//! `config.moderation.max_messages` within `window_secs`, issues an
+//! escalating response: a warning on the first offense, then a temporary
+//! mute via `restrict_chat_member` whose duration doubles on each
+//! subsequent offense (capped at `max_mute_secs`). An offense count decays
+//! back to zero after `offense_decay_secs` without a new one. A no-op
+//! entirely when `config.moderation.enabled` is `false`.
+
+use crate::bot::state::AppState;
+use crate::bot::utils::is_admin;
+use crate::config::Config;
+use crate::i18n::{Locale, MessageId, t};
+use chrono::Utc;
+use std::sync::Arc;
+use teloxide::payloads::RestrictChatMemberSetters;
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, ChatPermissions, UserId};
+use tokio::sync::Mutex;
+
+/// Checks `message`'s sender against the rate limit and, if they've just
+/// exceeded it, warns or mutes them. Returns `true` if the message should be
+/// treated as spam (callers should stop processing it any further).
+pub async fn enforce(
+    bot: &Bot,
+    message: &Message,
+    config: &Arc<Config>,
+    state: &Arc<Mutex<AppState>>,
+    locale: Locale,
+) -> Result<bool, anyhow::Error> {
+    if !config.moderation.enabled {
+        return Ok(false);
+    }
+    let chat_id = message.chat.id;
+    if !(chat_id.is_group() || chat_id.is_supergroup()) {
+        return Ok(false);
+    }
+    let Some(user) = message.from.as_ref() else {
+        return Ok(false);
+    };
+    if is_admin(bot, chat_id, user.id, config).await {
+        return Ok(false);
+    }
+
+    let key = (chat_id, user.id);
+    let now = Utc::now();
+    let window_start = now - chrono::Duration::seconds(config.moderation.window_secs as i64);
+
+    let exceeded = {
+        let mut state_guard = state.lock().await;
+        let timestamps = state_guard.message_activity.entry(key).or_default();
+        timestamps.push_back(now);
+        while timestamps.front().is_some_and(|&t| t < window_start) {
+            timestamps.pop_front();
+        }
+        timestamps.len() > config.moderation.max_messages as usize
+    };
+
+    if !exceeded {
+        return Ok(false);
+    }
+
+    let offense_count = {
+        let mut state_guard = state.lock().await;
+        let entry = state_guard.offenses.entry(key).or_insert((0, now));
+        let decay = chrono::Duration::seconds(config.moderation.offense_decay_secs as i64);
+        if now - entry.1 > decay {
+            entry.0 = 0;
+        }
+        entry.0 += 1;
+        entry.1 = now;
+        entry.0
+    };
+
+    if offense_count <= 1 {
+        bot.send_message(chat_id, t(locale, &MessageId::ModerationWarning))
+            .await?;
+    } else {
+        let mute_secs = config
+            .moderation
+            .base_mute_secs
+            .saturating_mul(1u64 << (offense_count - 2).min(63))
+            .min(config.moderation.max_mute_secs);
+        let until = now + chrono::Duration::seconds(mute_secs as i64);
+
+        bot.restrict_chat_member(chat_id, user.id, ChatPermissions::empty())
+            .until_date(until)
+            .await?;
+        state
+            .lock()
+            .await
+            .active_restrictions
+            .insert(key, until);
+
+        bot.send_message(
+            chat_id,
+            t(
+                locale,
+                &MessageId::ModerationMuted {
+                    minutes: mute_secs.div_ceil(60),
+                },
+            ),
+        )
+        .await?;
+    }
+
+    Ok(true)
+}
+
+/// Lifts an active moderation mute on `user_id` in `chat_id` early, if one
+/// is recorded. Returns `true` if a restriction was found and lifted.
+pub async fn pardon(
+    bot: &Bot,
+    chat_id: ChatId,
+    user_id: UserId,
+    state: &Arc<Mutex<AppState>>,
+) -> Result<bool, anyhow::Error> {
+    let key = (chat_id, user_id);
+    let had_restriction = state.lock().await.active_restrictions.remove(&key).is_some();
+    if !had_restriction {
+        return Ok(false);
+    }
+
+    bot.restrict_chat_member(chat_id, user_id, ChatPermissions::all())
+        .await?;
+    state.lock().await.offenses.remove(&key);
+    Ok(true)
+}
@@ -1,53 +1,103 @@
-use crate::qa::types::FormattedText;
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
-use teloxide::types::{ChatId, MessageId};
-
-/// Represents the current state of a pending QA addition.
-#[derive(Clone, Debug)]
-pub enum QAStatus {
-    /// The bot is waiting for an administrator to reply with an answer.
-    Answer { question: FormattedText },
-    /// The bot has received an answer and is waiting for confirmation.
-    Confirmation {
-        question: FormattedText,
-        answer: FormattedText,
-    },
-    /// Waiting for an admin to reply with the new question text.
-    EditQuestion {
-        old_question_hash: String,
-        original_answer: FormattedText,
-    },
-    /// Waiting for an admin to reply with the new answer text.
-    EditAnswer {
-        old_question_hash: String,
-        original_question: FormattedText,
-    },
-}
-
-/// Contains all information about a single pending QA process.
-#[derive(Clone, Debug)]
-pub struct PendingQAInfo {
-    /// The current status of the process.
-    pub status: QAStatus,
-}
+use crate::bot::dialogue::{self, DialogueStore};
+use crate::config::Config;
+use crate::i18n::Locale;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, VecDeque};
+use teloxide::types::{ChatId, UserId};
 
 /// The overall application state, shared across handlers.
 pub struct AppState {
-    /// A map for interactive QA processes.
-    pub pending_qas: HashMap<(ChatId, MessageId), PendingQAInfo>,
+    /// Persistent store for in-progress add/edit-QA conversations (see
+    /// `crate::bot::dialogue`).
+    pub dialogue_store: Box<dyn DialogueStore>,
     /// If Some, the bot will ignore generic messages until the specified time.
     pub snoozed_until: Option<DateTime<Utc>>,
     /// A flag to indicate if the QA system has finished its initial loading.
     pub is_qa_ready: bool,
+    /// Per-chat language override set via `/lang`. Chats not present here
+    /// fall back to `config.i18n.default_locale`.
+    pub chat_locales: HashMap<ChatId, Locale>,
+    /// Rolling count of `handle_generic_message` auto-replies that found a
+    /// match, since process start. Surfaced by `/stats`.
+    pub auto_reply_hits: u64,
+    /// Rolling count of `handle_generic_message` auto-replies that found no
+    /// match, since process start. Surfaced by `/stats`.
+    pub auto_reply_misses: u64,
+    /// Rolling count of errors returned while looking up a matching answer
+    /// (e.g. embedding-API failures), since process start. Surfaced by
+    /// `/metrics`.
+    pub embedding_errors: u64,
+    /// Recent message timestamps per `(chat, user)`, used by
+    /// `crate::bot::moderation` to detect spam bursts. Pruned to the
+    /// configured sliding window on each check.
+    pub message_activity: HashMap<(ChatId, UserId), VecDeque<DateTime<Utc>>>,
+    /// `(offense count, time of last offense)` per `(chat, user)`, used by
+    /// `crate::bot::moderation` to escalate mute durations on repeat
+    /// offenses and decay them back to zero after good behavior.
+    pub offenses: HashMap<(ChatId, UserId), (u32, DateTime<Utc>)>,
+    /// Expiry of each currently active moderation mute, so `/pardon` can
+    /// look up and lift a restriction early.
+    pub active_restrictions: HashMap<(ChatId, UserId), DateTime<Utc>>,
+    /// Ring buffer of each chat's recent user turns (text + receipt time),
+    /// used by `handle_generic_message` to build a multi-turn query when
+    /// `config.message.context_turns > 1`. Capped to `context_turns` entries
+    /// and pruned of turns older than `context_ttl_secs` on each read.
+    /// Reset early via `/clear`.
+    pub conversation_context: HashMap<ChatId, VecDeque<(String, DateTime<Utc>)>>,
 }
 
 impl AppState {
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
         Self {
-            pending_qas: HashMap::new(),
+            dialogue_store: dialogue::build_dialogue_store(config),
             snoozed_until: None,
             is_qa_ready: false, // Initial state is not ready.
+            chat_locales: HashMap::new(),
+            auto_reply_hits: 0,
+            auto_reply_misses: 0,
+            embedding_errors: 0,
+            message_activity: HashMap::new(),
+            offenses: HashMap::new(),
+            active_restrictions: HashMap::new(),
+            conversation_context: HashMap::new(),
+        }
+    }
+
+    /// Resolves the active locale for `chat_id`, falling back to `default`.
+    pub fn locale_for(&self, chat_id: ChatId, default: Locale) -> Locale {
+        self.chat_locales.get(&chat_id).copied().unwrap_or(default)
+    }
+
+    /// Appends `text` to `chat_id`'s context buffer, evicting the oldest
+    /// turn once the buffer holds more than `max_turns` entries.
+    pub fn push_context_turn(&mut self, chat_id: ChatId, text: String, max_turns: usize) {
+        let buf = self.conversation_context.entry(chat_id).or_default();
+        buf.push_back((text, Utc::now()));
+        while buf.len() > max_turns.max(1) {
+            buf.pop_front();
         }
     }
+
+    /// Builds the combined multi-turn query for `chat_id`: turns older than
+    /// `ttl_secs` are dropped first, then the remaining turns are joined in
+    /// chronological order. Returns `None` if nothing fresh remains.
+    pub fn context_query(&mut self, chat_id: ChatId, ttl_secs: i64) -> Option<String> {
+        let buf = self.conversation_context.get_mut(&chat_id)?;
+        let cutoff = Utc::now() - Duration::seconds(ttl_secs);
+        buf.retain(|(_, turn_time)| *turn_time >= cutoff);
+        if buf.is_empty() {
+            return None;
+        }
+        Some(
+            buf.iter()
+                .map(|(text, _)| text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    /// Resets `chat_id`'s conversation context, e.g. via `/clear`.
+    pub fn clear_context(&mut self, chat_id: ChatId) {
+        self.conversation_context.remove(&chat_id);
+    }
 }
@@ -3,14 +3,13 @@
 //! Defines shared types for the bot module, particularly for handling
 //! type-safe callback queries.
 
-use serde::{Deserialize, Serialize};
-
 /// Represents the various actions that can be triggered from an inline keyboard.
 ///
-/// This enum is serialized into a JSON string for callback data, providing a
-/// type-safe way to handle different user interactions, instead of relying on
-/// fragile string parsing.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Encoded via [`CallbackData::encode`]/[`CallbackData::decode`] into a
+/// compact `opcode short_hash` string instead of JSON, since Telegram hard-
+/// limits `callback_data` to 64 bytes and the JSON wrapper alone ate up most
+/// of that budget before `short_hash` even started.
+#[derive(Debug, Clone)]
 pub enum CallbackData {
     /// View the details of a specific QA item. Payload is the short hash.
     ViewQa { short_hash: String },
@@ -28,4 +27,67 @@ pub enum CallbackData {
     Reedit,
     /// Cancel the current multi-step operation (e.g., add/edit QA).
     Cancel,
+    /// Navigate the `/list` browsing keyboard to `page` (0-indexed).
+    ListPage { page: usize },
+}
+
+impl CallbackData {
+    /// Encodes `self` as a compact `callback_data` payload: a one- or
+    /// two-char opcode, followed by a space and the raw hash for variants
+    /// that carry one. Payload-less variants are a single character.
+    pub fn encode(&self) -> String {
+        let encoded = match self {
+            Self::ViewQa { short_hash } => format!("v {short_hash}"),
+            Self::DeletePrompt { short_hash } => format!("dp {short_hash}"),
+            Self::DeleteConfirm { short_hash } => format!("dc {short_hash}"),
+            Self::EditQuestionPrompt { short_hash } => format!("eq {short_hash}"),
+            Self::EditAnswerPrompt { short_hash } => format!("ea {short_hash}"),
+            Self::Confirm => "C".to_string(),
+            Self::Reedit => "R".to_string(),
+            Self::Cancel => "X".to_string(),
+            Self::ListPage { page } => format!("lp {page}"),
+        };
+        debug_assert!(
+            encoded.len() <= 64,
+            "callback_data {encoded:?} exceeds Telegram's 64-byte limit"
+        );
+        encoded
+    }
+
+    /// Decodes a `callback_data` payload produced by [`Self::encode`].
+    pub fn decode(data: &str) -> anyhow::Result<Self> {
+        if let Some((opcode, short_hash)) = data.split_once(' ') {
+            return match opcode {
+                "v" => Ok(Self::ViewQa {
+                    short_hash: short_hash.to_string(),
+                }),
+                "dp" => Ok(Self::DeletePrompt {
+                    short_hash: short_hash.to_string(),
+                }),
+                "dc" => Ok(Self::DeleteConfirm {
+                    short_hash: short_hash.to_string(),
+                }),
+                "eq" => Ok(Self::EditQuestionPrompt {
+                    short_hash: short_hash.to_string(),
+                }),
+                "ea" => Ok(Self::EditAnswerPrompt {
+                    short_hash: short_hash.to_string(),
+                }),
+                "lp" => {
+                    let page = short_hash
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("invalid ListPage page: {short_hash:?}"))?;
+                    Ok(Self::ListPage { page })
+                }
+                other => anyhow::bail!("unknown callback_data opcode: {other:?}"),
+            };
+        }
+
+        match data {
+            "C" => Ok(Self::Confirm),
+            "R" => Ok(Self::Reedit),
+            "X" => Ok(Self::Cancel),
+            other => anyhow::bail!("unrecognized callback_data: {other:?}"),
+        }
+    }
 }
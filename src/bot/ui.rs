@@ -8,10 +8,9 @@
 use crate::bot::types::CallbackData;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
-/// Serializes a CallbackData enum into a JSON string for use in an InlineKeyboardButton.
-/// Panics on failure, as serialization of the internal enum should never fail.
+/// Encodes a CallbackData enum into a compact string for use in an InlineKeyboardButton.
 fn create_callback_data(data: CallbackData) -> String {
-    serde_json::to_string(&data).expect("Failed to serialize callback data")
+    data.encode()
 }
 
 // --- Single Buttons ---
@@ -114,3 +113,58 @@ pub fn simple_cancel_keyboard() -> InlineKeyboardMarkup {
 pub fn reedit_keyboard() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(vec![vec![cancel_button()]])
 }
+
+/// Creates a paginated browsing keyboard for `/list`.
+///
+/// `items` holds every QA item as `(short_hash, question_preview)`; only the
+/// slice belonging to `page` (0-indexed, `per_page` items wide) is rendered,
+/// laid out three buttons per row, with a trailing nav row carrying whichever
+/// of "◀ Prev"/"Next ▶" are valid for that page.
+pub fn qa_list_keyboard(
+    items: &[(String, String)],
+    page: usize,
+    per_page: usize,
+) -> InlineKeyboardMarkup {
+    let start = page * per_page;
+    let page_items = items.get(start..).unwrap_or(&[]);
+    let page_items = &page_items[..page_items.len().min(per_page)];
+
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = page_items
+        .chunks(3)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|(short_hash, preview)| {
+                    InlineKeyboardButton::callback(
+                        preview.clone(),
+                        CallbackData::ViewQa {
+                            short_hash: short_hash.clone(),
+                        }
+                        .encode(),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    let has_prev = page > 0;
+    let has_next = start + per_page < items.len();
+    if has_prev || has_next {
+        let mut nav_row = Vec::with_capacity(2);
+        if has_prev {
+            nav_row.push(InlineKeyboardButton::callback(
+                "◀ Prev",
+                CallbackData::ListPage { page: page - 1 }.encode(),
+            ));
+        }
+        if has_next {
+            nav_row.push(InlineKeyboardButton::callback(
+                "Next ▶",
+                CallbackData::ListPage { page: page + 1 }.encode(),
+            ));
+        }
+        rows.push(nav_row);
+    }
+
+    InlineKeyboardMarkup::new(rows)
+}
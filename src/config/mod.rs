@@ -5,6 +5,13 @@ mod types;
 
 pub use types::*;
 
+/// Path to the TOML file [`load_user_config`] reads, so callers that need to
+/// watch it for changes (see [`crate::qa::watcher`]) don't have to re-derive the config
+/// directory themselves.
+pub fn config_file_path() -> Result<PathBuf> {
+    Ok(get_config_directory()?.join("config.toml"))
+}
+
 pub fn load_user_config() -> Result<Config> {
     let config_dir = get_config_directory()?;
     let config_file_path = config_dir.join("config.toml");
@@ -10,6 +10,17 @@ pub struct Config {
     pub message: MessageConfig,
     pub log_level: LogLevel,
     pub qa: QaConfig,
+    pub generation: GenerationConfig,
+    pub index: IndexConfig,
+    pub storage: StorageConfig,
+    pub gossip: GossipConfig,
+    pub ocr: OcrConfig,
+    pub i18n: I18nConfig,
+    pub hybrid_search: HybridSearchConfig,
+    pub moderation: ModerationConfig,
+    pub dialogue: DialogueConfig,
+    pub admin: AdminConfig,
+    pub rate_limit: RateLimitConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -83,6 +94,14 @@ impl Default for SimilarityConfig {
 pub struct MessageConfig {
     pub delete_delay: u64,
     pub timeout: i64,
+    /// How many recent user turns to concatenate into the query sent to the
+    /// embedder, so a follow-up like "and what about cargo?" carries enough
+    /// signal to clear `similarity.threshold` on its own. `1` (the default)
+    /// keeps the existing single-message behavior; the buffer is opt-in.
+    pub context_turns: usize,
+    /// Seconds a turn may sit in a chat's context buffer before it's
+    /// considered stale and dropped from future queries.
+    pub context_ttl_secs: i64,
 }
 
 impl Default for MessageConfig {
@@ -90,6 +109,8 @@ impl Default for MessageConfig {
         Self {
             delete_delay: 10,
             timeout: 60,
+            context_turns: 1,
+            context_ttl_secs: 120,
         }
     }
 }
@@ -125,12 +146,374 @@ impl From<LogLevel> for log::Level {
 #[serde(default)]
 pub struct QaConfig {
     pub qa_json_path: String,
+    /// Target chunk size (in characters) used when ingesting a long document.
+    pub ingest_chunk_chars: usize,
+    /// Character overlap between consecutive chunks of an ingested document.
+    pub ingest_chunk_overlap_chars: usize,
 }
 
 impl Default for QaConfig {
     fn default() -> Self {
         Self {
             qa_json_path: "data/QA.json".to_string(),
+            ingest_chunk_chars: 800,
+            ingest_chunk_overlap_chars: 100,
+        }
+    }
+}
+
+/// Controls the optional retrieval-augmented-generation answer mode.
+///
+/// When `enabled` is `false` (the default), the bot keeps using the existing
+/// top-1 exact-match behavior so current deployments are unaffected.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GenerationConfig {
+    pub enabled: bool,
+    /// Number of top-matching QA items to retrieve as context for synthesis.
+    pub top_k: usize,
+    /// Gemini model used to synthesize the final answer from retrieved context.
+    pub model: String,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            top_k: 3,
+            model: "gemini-1.5-flash".to_string(),
+        }
+    }
+}
+
+/// Which `VectorIndex` backend to search `question_embeddings` with.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum IndexBackend {
+    /// The original O(n) cosine scan. Exact, and fast enough for small corpora.
+    BruteForce,
+    /// Approximate nearest-neighbor search via a Hierarchical Navigable Small
+    /// World graph. Scales far better once the QA corpus grows large.
+    Hnsw,
+}
+
+impl Default for IndexBackend {
+    fn default() -> Self {
+        Self::BruteForce
+    }
+}
+
+/// Tuning knobs for the nearest-neighbor index used to look up QA embeddings.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IndexConfig {
+    pub backend: IndexBackend,
+    /// Max neighbors per node per layer (doubled at layer 0).
+    pub m: usize,
+    /// Candidate list size used while building the graph.
+    pub ef_construction: usize,
+    /// Candidate list size used while searching the graph.
+    pub ef_search: usize,
+    /// Below this many QA items, use `BruteForceIndex` even when `backend` is
+    /// `Hnsw`: an exact linear scan is already fast at this scale, and skips
+    /// the graph-construction overhead and approximation error HNSW brings.
+    pub hnsw_min_items: usize,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            backend: IndexBackend::BruteForce,
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+            hnsw_min_items: 1000,
+        }
+    }
+}
+
+/// Which [`crate::qa::store::Store`] backend holds the QA items and the
+/// embeddings cache.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// The original local JSON files under `qa.qa_json_path` / `cache.dir`.
+    LocalFs,
+    /// An S3-compatible object-storage bucket, so multiple bot instances can
+    /// share one QA corpus and embeddings cache.
+    S3,
+    /// A local SQLite database for the embeddings cache, addressed by
+    /// `(question_hash, model_name)` instead of a single JSON blob. QA items
+    /// themselves are still stored in the `qa.qa_json_path` JSON file.
+    Sqlite,
+}
+
+impl Default for StorageBackend {
+    fn default() -> Self {
+        Self::LocalFs
+    }
+}
+
+/// Settings for the pluggable QA data / embeddings cache storage backend.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    /// Bucket name. Only used when `backend` is `S3`.
+    pub s3_bucket: String,
+    /// Key prefix under which QA items and embeddings are stored, e.g.
+    /// `telembed/prod`. Only used when `backend` is `S3`.
+    pub s3_prefix: String,
+    /// AWS region, or any placeholder region accepted by the endpoint below.
+    pub s3_region: String,
+    /// Optional custom endpoint for S3-compatible providers (MinIO, R2, ...).
+    /// Leave empty to use AWS's default endpoint for `s3_region`.
+    pub s3_endpoint: String,
+    /// Path to the SQLite database file. Only used when `backend` is
+    /// `Sqlite`. Relative paths are resolved under `cache.dir`.
+    pub sqlite_path: String,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            backend: StorageBackend::LocalFs,
+            s3_bucket: String::new(),
+            s3_prefix: "telembed".to_string(),
+            s3_region: "us-east-1".to_string(),
+            s3_endpoint: String::new(),
+            sqlite_path: "embeddings.sqlite3".to_string(),
+        }
+    }
+}
+
+/// Settings for the optional UDP peer-gossip subsystem that propagates QA
+/// mutations (`add_qa`/`update_qa`/`delete_qa`) to other bot replicas.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GossipConfig {
+    pub enabled: bool,
+    /// Local address to bind the gossip UDP socket to.
+    pub listen_addr: String,
+    /// Addresses (`host:port`) of peer replicas to broadcast mutations to.
+    pub peers: Vec<String>,
+    /// Shared secret included in every datagram; mismatches are dropped.
+    pub auth_token: String,
+    /// How long a `(node_id, counter)` pair is remembered to reject
+    /// duplicate deliveries of the same event.
+    pub dedup_ttl_secs: u64,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: "0.0.0.0:7879".to_string(),
+            peers: vec![],
+            auth_token: String::new(),
+            dedup_ttl_secs: 300,
+        }
+    }
+}
+
+/// Settings for OCR over image messages (see `crate::ocr`), so a question or
+/// answer can be captured from a screenshot instead of typed text.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OcrConfig {
+    pub enabled: bool,
+    /// Tesseract language codes to recognize, e.g. `["eng", "chi_sim"]` for
+    /// mixed English/Chinese community screenshots.
+    pub languages: Vec<String>,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            languages: vec!["eng".to_string(), "chi_sim".to_string()],
+        }
+    }
+}
+
+/// Settings for localized bot replies (see `crate::i18n`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct I18nConfig {
+    /// Locale used for chats that haven't set one of their own via `/lang`.
+    pub default_locale: crate::i18n::Locale,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            default_locale: crate::i18n::Locale::default(),
+        }
+    }
+}
+
+/// Tuning knobs for the optional hybrid keyword+vector retriever used by
+/// `QAService::find_matching_qa` (see [`crate::qa::search::reciprocal_rank_fusion`]).
+///
+/// When `enabled` is `false` (the default), `find_matching_qa` keeps using
+/// the original pure vector-similarity lookup so existing deployments are
+/// unaffected.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HybridSearchConfig {
+    pub enabled: bool,
+    /// How many top vector and keyword candidates to fuse per query.
+    pub candidates: usize,
+    /// Reciprocal Rank Fusion's `k` constant; higher values flatten the
+    /// influence of rank position on the fused score.
+    pub rrf_k: f64,
+    /// Minimum fused RRF score the top candidate must clear to be accepted.
+    pub fused_threshold: f64,
+    /// Weight (0.0–1.0) given to the vector-similarity ranking's
+    /// contribution to the fused score; the keyword ranking gets
+    /// `1.0 - semantic_ratio`. `0.5` weighs both equally; push it toward
+    /// `1.0` to favor semantic matches or toward `0.0` to favor keyword
+    /// matches.
+    pub semantic_ratio: f64,
+}
+
+impl Default for HybridSearchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            candidates: 10,
+            rrf_k: 60.0,
+            fused_threshold: 0.0,
+            semantic_ratio: 0.5,
+        }
+    }
+}
+
+/// Settings for the anti-spam moderation throttle applied to non-admins in
+/// group chats (see `crate::bot::moderation`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    pub enabled: bool,
+    /// Max messages a non-admin may send within `window_secs` before being
+    /// treated as spamming.
+    pub max_messages: u32,
+    /// Sliding window, in seconds, over which `max_messages` is counted.
+    pub window_secs: u64,
+    /// Mute duration applied on a user's first muted offense (their second
+    /// offense overall; the first offense only gets a warning).
+    pub base_mute_secs: u64,
+    /// Cap on the mute duration, however many consecutive offenses a user
+    /// racks up.
+    pub max_mute_secs: u64,
+    /// How long a user must go without a new offense before their offense
+    /// count decays back to zero.
+    pub offense_decay_secs: u64,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_messages: 8,
+            window_secs: 30,
+            base_mute_secs: 5 * 60,
+            max_mute_secs: 60 * 60,
+            offense_decay_secs: 60 * 60,
+        }
+    }
+}
+
+/// Settings for the per-(chat, user) request throttle applied ahead of every
+/// handler by `crate::bot::hooks::RateLimitHook`. Distinct from
+/// `ModerationConfig`, which escalates to muting spammers in group chats;
+/// this just rejects updates outright once the rate is exceeded, regardless
+/// of chat type.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Max updates a single `(chat, user)` pair may send within `window_secs`.
+    pub max_requests: u32,
+    /// Sliding window, in seconds, over which `max_requests` is counted.
+    pub window_secs: u64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_requests: 20,
+            window_secs: 60,
+        }
+    }
+}
+
+/// Which [`crate::bot::dialogue::DialogueStore`] backend holds in-progress
+/// add/edit-QA conversations.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum DialogueBackend {
+    /// Conversations live only for the life of the process.
+    InMemory,
+    /// Conversations are persisted to a JSON file so they survive a restart.
+    JsonFile,
+    /// Conversations are persisted to Redis, so multiple bot replicas can
+    /// share in-progress conversations instead of each keeping its own.
+    Redis,
+}
+
+impl Default for DialogueBackend {
+    fn default() -> Self {
+        Self::InMemory
+    }
+}
+
+/// Settings for the interactive add/edit-QA conversation state machine (see
+/// `crate::bot::dialogue`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DialogueConfig {
+    pub backend: DialogueBackend,
+    /// Path to the JSON file backing conversations. Only used when `backend`
+    /// is `JsonFile`.
+    pub json_path: String,
+    /// Redis connection URL, e.g. `redis://127.0.0.1:6379`. Only used when
+    /// `backend` is `Redis`.
+    pub redis_url: String,
+    /// Key prefix for entries written to Redis, so multiple deployments can
+    /// share one Redis instance. Only used when `backend` is `Redis`.
+    pub redis_key_prefix: String,
+    /// How long an inactive conversation is kept before it's treated as
+    /// abandoned and pruned. For the `Redis` backend this is applied as a
+    /// native key TTL instead of a background sweep.
+    pub ttl_secs: u64,
+}
+
+impl Default for DialogueConfig {
+    fn default() -> Self {
+        Self {
+            backend: DialogueBackend::InMemory,
+            json_path: "cache/dialogues.json".to_string(),
+            redis_url: "redis://127.0.0.1:6379".to_string(),
+            redis_key_prefix: "telembed:dialogue".to_string(),
+            ttl_secs: 3600,
+        }
+    }
+}
+
+/// Settings for the admin HTTP server exposing `/metrics` (Prometheus text
+/// exposition format) and `/healthz` (liveness probe), see `crate::metrics`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AdminConfig {
+    pub enabled: bool,
+    /// Address the admin HTTP server binds to, e.g. `127.0.0.1:9090`.
+    pub bind_addr: String,
+}
+
+impl Default for AdminConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_addr: "127.0.0.1:9090".to_string(),
         }
     }
 }
@@ -0,0 +1,89 @@
+use crate::{
+    config::Config,
+    gemini::key_manager::{GeminiKeyManager, KeyFailure},
+};
+use anyhow::{Result, anyhow};
+use rig::{completion::Prompt, providers::gemini::Client as GeminiClient};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// 使用 rig 和 Gemini API 根据给定的 prompt 生成一段文本回答
+async fn generate_single_answer(api_key: &str, model: &str, prompt: &str) -> Result<String> {
+    let gemini_client = GeminiClient::new(api_key);
+    let agent = gemini_client.agent(model).build();
+    let response = agent
+        .prompt(prompt)
+        .await
+        .map_err(|e| anyhow!("Gemini generation request failed: {e}"))?;
+    Ok(response)
+}
+
+/// 生成最终回答文本，包含重试和 API Key 管理逻辑，复用 embedding 模块的重试策略。
+pub async fn generate_answer_with_retry(
+    config: &Config,
+    key_manager: &Arc<GeminiKeyManager>,
+    prompt: &str,
+) -> Result<String> {
+    const MAX_ATTEMPTS: u32 = 10;
+    let mut attempts = 0;
+
+    loop {
+        if attempts >= MAX_ATTEMPTS {
+            return Err(anyhow!(
+                "Failed to generate an answer after {} attempts.",
+                MAX_ATTEMPTS
+            ));
+        }
+        attempts += 1;
+
+        let api_key = match key_manager.get_key() {
+            Ok(key) => key,
+            Err(e) => {
+                let wait = key_manager
+                    .min_cooldown_remaining()
+                    .unwrap_or(Duration::from_secs(60));
+                log::error!("Could not get API key: {}. Retrying in {:?}.", e, wait);
+                sleep(wait).await;
+                continue;
+            }
+        };
+
+        match generate_single_answer(&api_key, &config.generation.model, prompt).await {
+            Ok(answer) => {
+                key_manager.report_success(&api_key);
+                return Ok(answer);
+            }
+            Err(e) => {
+                let error_string = e.to_string().to_lowercase();
+                if error_string.contains("429")
+                    || error_string.contains("resource has been exhausted")
+                {
+                    log::warn!(
+                        "API key rate-limited during generation. Cooling it down. Error: {}",
+                        e
+                    );
+                    key_manager.report_rate_limited(&api_key);
+                    continue;
+                } else if error_string.contains("401") || error_string.contains("403") {
+                    log::warn!("API key unauthorized during generation. Error: {}", e);
+                    key_manager.report_failure(&api_key, KeyFailure::Unauthorized);
+                    continue;
+                } else if error_string.contains("500")
+                    || error_string.contains("502")
+                    || error_string.contains("503")
+                    || error_string.contains("504")
+                {
+                    log::warn!("Gemini server error during generation. Error: {}", e);
+                    key_manager.report_failure(&api_key, KeyFailure::ServerError);
+                    sleep(Duration::from_secs(5)).await;
+                    continue;
+                } else {
+                    log::error!("Failed to generate answer: {}. Retrying in 5s...", e);
+                    sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,189 @@
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Cooldown applied the first time a key is rate-limited; doubles on each
+/// consecutive 429 up to `GeminiKeyManager`'s `max_cooldown`.
+const BASE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Default cap on a key's cooldown, however many consecutive 429s it's hit.
+const DEFAULT_MAX_COOLDOWN: Duration = Duration::from_secs(15 * 60);
+
+/// Consecutive non-429 failures (401/403, 5xx, transport errors) before a
+/// key is quarantined outright, separate from the 429 cooldown above.
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// Quarantine applied the first time a key trips the circuit breaker;
+/// doubles on each re-quarantine (including a failed health probe) up to
+/// `MAX_QUARANTINE`.
+const BASE_QUARANTINE: Duration = Duration::from_secs(60);
+
+/// Cap on a key's quarantine, however many times it's been re-quarantined.
+const MAX_QUARANTINE: Duration = Duration::from_secs(60 * 60);
+
+/// Classifies an embedding/generation call failure so [`GeminiKeyManager`]
+/// can decide whether a key's circuit breaker should trip. Distinct from a
+/// 429, which already has its own cooldown handling via
+/// [`GeminiKeyManager::report_rate_limited`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyFailure {
+    /// 401/403: the key itself appears to be invalid or revoked.
+    Unauthorized,
+    /// 5xx or a transport-level error: the service seems unwell.
+    ServerError,
+}
+
+/// A key's circuit-breaker state, layered on top of the RPM/RPD and 429-
+/// cooldown bookkeeping above. Tracks keys that are failing outright
+/// (revoked, sustained errors) rather than merely rate-limited.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum KeyHealth {
+    /// Selectable as normal.
+    Healthy,
+    /// Quarantine just expired; the next selection gets exactly one trial
+    /// request before `report_failure`/`report_success` decides whether to
+    /// re-quarantine or promote back to `Healthy`.
+    Probing,
+    /// Skipped by selection until `until` passes.
+    Quarantined { until: DateTime<Utc> },
+}
+
+impl Default for KeyHealth {
+    fn default() -> Self {
+        KeyHealth::Healthy
+    }
+}
 
 #[derive(Debug, Clone)]
 struct ApiKey {
     key: String,
+    /// `None` while the key is usable; `Some(instant)` while it's cooling
+    /// down from a 429. `get_key` re-enables it on its own once this time
+    /// has passed, rather than requiring a manual re-enable.
     disabled_until: Option<DateTime<Utc>>,
+    /// Cooldown applied the *next* time this key is rate-limited. Doubles
+    /// (capped at `max_cooldown`) on each consecutive 429 and resets back to
+    /// `BASE_COOLDOWN` after a successful call.
+    next_cooldown: Duration,
     requests: Vec<DateTime<Utc>>, // Track request timestamps
+    /// Circuit-breaker state; see [`KeyHealth`].
+    health: KeyHealth,
+    /// Consecutive non-429 failures since the last success or promotion.
+    consecutive_failures: u32,
+    /// Quarantine applied the *next* time this key trips the breaker.
+    /// Doubles (capped at `MAX_QUARANTINE`) on each re-quarantine and resets
+    /// back to `BASE_QUARANTINE` after a successful call.
+    quarantine_cooldown: Duration,
+}
+
+/// On-disk shape of a single key's state, written to the sidecar state file
+/// so RPD accounting and 429 cooldowns survive a process restart. Keyed by
+/// the key string itself rather than an index, so reordering/adding/removing
+/// keys in config doesn't scramble another key's saved state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedApiKeyState {
+    key: String,
+    disabled_until: Option<DateTime<Utc>>,
+    next_cooldown_secs: u64,
+    requests: Vec<DateTime<Utc>>,
+    #[serde(default)]
+    health: KeyHealth,
+    #[serde(default)]
+    consecutive_failures: u32,
+    #[serde(default = "default_quarantine_cooldown_secs")]
+    quarantine_cooldown_secs: u64,
+}
+
+/// Serde default for `quarantine_cooldown_secs`, so state files written
+/// before the circuit breaker existed still parse.
+fn default_quarantine_cooldown_secs() -> u64 {
+    BASE_QUARANTINE.as_secs()
+}
+
+/// Returns the path to the sidecar file `GeminiKeyManager` persists its
+/// per-key rate-limit state to, alongside the embeddings cache in
+/// `cache_dir`.
+fn state_file_path(cache_dir: &str) -> PathBuf {
+    Path::new(cache_dir).join("gemini_key_state.json")
+}
+
+/// Loads previously persisted key state, if any. Missing or unparsable state
+/// is treated as "nothing saved yet" rather than a hard error, so a fresh
+/// deployment or a corrupted sidecar file doesn't prevent startup.
+fn load_state(path: &Path) -> Vec<PersistedApiKeyState> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!(
+            "Failed to parse Gemini key-manager state file at {}, starting fresh: {}",
+            path.display(),
+            e
+        );
+        Vec::new()
+    })
+}
+
+/// Persists the current state of every key. Best-effort: a write failure is
+/// logged rather than propagated, since losing rate-limit bookkeeping is
+/// preferable to breaking the bot over a disk hiccup.
+fn save_state(path: &Path, keys: &[ApiKey]) {
+    let persisted: Vec<PersistedApiKeyState> = keys
+        .iter()
+        .map(|k| PersistedApiKeyState {
+            key: k.key.clone(),
+            disabled_until: k.disabled_until,
+            next_cooldown_secs: k.next_cooldown.as_secs(),
+            requests: k.requests.clone(),
+            health: k.health,
+            consecutive_failures: k.consecutive_failures,
+            quarantine_cooldown_secs: k.quarantine_cooldown.as_secs(),
+        })
+        .collect();
+
+    let json = match serde_json::to_string_pretty(&persisted) {
+        Ok(json) => json,
+        Err(e) => {
+            log::warn!("Failed to serialize Gemini key-manager state: {}", e);
+            return;
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            log::warn!(
+                "Failed to create directory for Gemini key-manager state ({}): {}",
+                parent.display(),
+                e
+            );
+            return;
+        }
+    }
+
+    if let Err(e) = fs::write(path, json) {
+        log::warn!(
+            "Failed to persist Gemini key-manager state to {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+/// A single key's request counts and remaining budget, returned by
+/// [`GeminiKeyManager::key_metrics`].
+#[derive(Debug, Clone)]
+pub struct KeyMetrics {
+    pub index: usize,
+    pub requests_last_minute: u32,
+    pub requests_last_day: u32,
+    pub rpm_remaining: u32,
+    pub rpd_remaining: u32,
+    pub cooling_down: bool,
+    pub quarantined: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -15,26 +192,87 @@ pub struct GeminiKeyManager {
     last_used_key_index: Arc<Mutex<usize>>,
     rpm_limit: u32,
     rpd_limit: u32,
+    max_cooldown: Duration,
+    /// Where per-key rate-limit state is persisted; see [`save_state`].
+    state_path: PathBuf,
 }
 
 impl GeminiKeyManager {
-    pub fn new(api_keys: Vec<String>, rpm_limit: u32, rpd_limit: u32) -> Self {
+    /// `cache_dir` should be the same directory used for the embeddings
+    /// cache (`config.cache.dir`); the key-manager state lives alongside it
+    /// as its own sidecar file.
+    pub fn new(api_keys: Vec<String>, rpm_limit: u32, rpd_limit: u32, cache_dir: &str) -> Self {
+        Self::with_max_cooldown(api_keys, rpm_limit, rpd_limit, DEFAULT_MAX_COOLDOWN, cache_dir)
+    }
+
+    pub fn with_max_cooldown(
+        api_keys: Vec<String>,
+        rpm_limit: u32,
+        rpd_limit: u32,
+        max_cooldown: Duration,
+        cache_dir: &str,
+    ) -> Self {
+        let state_path = state_file_path(cache_dir);
+        let persisted = load_state(&state_path);
+        let now = Utc::now();
+        let one_day_ago = now - chrono::Duration::days(1);
+
         let keys = api_keys
             .into_iter()
-            .map(|key| ApiKey {
-                key,
-                disabled_until: None,
-                requests: Vec::new(),
+            .map(|key| match persisted.iter().find(|saved| saved.key == key) {
+                // Restore state for a key we've seen before, pruning request
+                // timestamps that have aged out of the 24h RPD window and
+                // only honoring `disabled_until` (or a saved quarantine) if
+                // it's still in the future.
+                Some(saved) => {
+                    let health = match saved.health {
+                        KeyHealth::Quarantined { until } if until <= now => KeyHealth::Probing,
+                        other => other,
+                    };
+                    ApiKey {
+                        key,
+                        disabled_until: saved.disabled_until.filter(|&until| until > now),
+                        next_cooldown: Duration::from_secs(saved.next_cooldown_secs)
+                            .max(BASE_COOLDOWN),
+                        requests: saved
+                            .requests
+                            .iter()
+                            .copied()
+                            .filter(|&t| t > one_day_ago)
+                            .collect(),
+                        health,
+                        consecutive_failures: saved.consecutive_failures,
+                        quarantine_cooldown: Duration::from_secs(saved.quarantine_cooldown_secs)
+                            .max(BASE_QUARANTINE),
+                    }
+                }
+                None => ApiKey {
+                    key,
+                    disabled_until: None,
+                    next_cooldown: BASE_COOLDOWN,
+                    requests: Vec::new(),
+                    health: KeyHealth::Healthy,
+                    consecutive_failures: 0,
+                    quarantine_cooldown: BASE_QUARANTINE,
+                },
             })
             .collect();
+
         Self {
             keys: Arc::new(Mutex::new(keys)),
             last_used_key_index: Arc::new(Mutex::new(0)),
             rpm_limit,
             rpd_limit,
+            max_cooldown,
+            state_path,
         }
     }
 
+    /// Persists the current state of every key to `self.state_path`.
+    fn persist(&self, keys: &[ApiKey]) {
+        save_state(&self.state_path, keys);
+    }
+
     pub fn get_key(&self) -> Result<String> {
         let mut keys_guard = self.keys.lock().unwrap();
         let now = Utc::now();
@@ -42,7 +280,7 @@ impl GeminiKeyManager {
 
         // First, iterate and update status of all keys (re-enable, prune old requests)
         for api_key in keys_guard.iter_mut() {
-            // Re-enable keys disabled by 429 errors if the time has passed.
+            // Re-enable keys whose cooldown from a 429 has elapsed.
             if let Some(disabled_until) = api_key.disabled_until {
                 if now >= disabled_until {
                     api_key.disabled_until = None;
@@ -52,6 +290,17 @@ impl GeminiKeyManager {
                     );
                 }
             }
+            // Move a quarantined key to `Probing` once its cooldown elapses,
+            // so the next selection gives it exactly one trial request.
+            if let KeyHealth::Quarantined { until } = api_key.health {
+                if now >= until {
+                    api_key.health = KeyHealth::Probing;
+                    log::info!(
+                        "API key ending in ...{} is due for a health probe.",
+                        api_key.key.chars().rev().take(4).collect::<String>()
+                    );
+                }
+            }
             // Prune request timestamps older than 24 hours to keep the list small.
             api_key.requests.retain(|&t| t > one_day_ago);
         }
@@ -72,6 +321,8 @@ impl GeminiKeyManager {
                 let api_key = &keys_guard[idx];
                 if api_key.disabled_until.is_some() {
                     false
+                } else if matches!(api_key.health, KeyHealth::Quarantined { .. }) {
+                    false
                 } else if api_key.requests.len() >= self.rpd_limit as usize {
                     false
                 } else {
@@ -89,30 +340,163 @@ impl GeminiKeyManager {
                 let api_key = &mut keys_guard[idx];
                 api_key.requests.push(now);
                 *last_idx = idx;
-                return Ok(api_key.key.clone());
+                self.persist(&keys_guard);
+                return Ok(keys_guard[idx].key.clone());
             }
         }
 
+        self.persist(&keys_guard);
+        if !keys_guard.is_empty()
+            && keys_guard
+                .iter()
+                .all(|k| matches!(k.health, KeyHealth::Quarantined { .. }))
+        {
+            return Err(anyhow!(
+                "All API keys are quarantined after repeated failures; none are safe to use right now."
+            ));
+        }
         Err(anyhow!(
             "All API keys are currently rate-limited or disabled."
         ))
     }
 
-    pub fn disable_key(&self, key_to_disable: &str) {
+    /// When [`Self::get_key`] fails because every key is cooling down from a
+    /// 429, returns how long until the soonest one recovers, so a retry loop
+    /// can sleep exactly that long instead of a fixed guess. Returns `None`
+    /// if no key is disabled (e.g. they're all daily-quota-exhausted instead).
+    pub fn min_cooldown_remaining(&self) -> Option<Duration> {
+        let keys_guard = self.keys.lock().unwrap();
+        let now = Utc::now();
+        keys_guard
+            .iter()
+            .filter_map(|k| k.disabled_until)
+            .map(|until| (until - now).to_std().unwrap_or(Duration::ZERO))
+            .min()
+    }
+
+    /// Puts `key` into cooldown after a 429, doubling its backoff from the
+    /// last time this happened (capped at `max_cooldown`) so repeated rate
+    /// limiting backs off exponentially instead of disabling the key for a
+    /// fixed, arbitrarily long period.
+    pub fn report_rate_limited(&self, key: &str) {
         let mut keys = self.keys.lock().unwrap();
-        if let Some(api_key) = keys.iter_mut().find(|k| k.key == key_to_disable) {
-            // Disable the key until midnight UTC of the next day.
-            let now = Utc::now();
-            let tomorrow = (now.date_naive() + chrono::Duration::days(1))
-                .and_hms_opt(0, 0, 0)
-                .unwrap();
-            let tomorrow_utc = DateTime::<Utc>::from_naive_utc_and_offset(tomorrow, Utc);
-            api_key.disabled_until = Some(tomorrow_utc);
+        if let Some(api_key) = keys.iter_mut().find(|k| k.key == key) {
+            let cooldown = api_key.next_cooldown;
+            api_key.disabled_until = Some(Utc::now() + chrono::Duration::from_std(cooldown).unwrap());
+            api_key.next_cooldown = (cooldown * 2).min(self.max_cooldown);
             log::warn!(
-                "Disabling API key ending in ...{} until {}",
+                "Cooling down API key ending in ...{} for {:?}",
                 api_key.key.chars().rev().take(4).collect::<String>(),
-                tomorrow_utc
+                cooldown
             );
         }
+        self.persist(&keys);
+    }
+
+    /// Records a non-429 failure (401/403, 5xx, or a transport error)
+    /// against `key`. Trips the circuit breaker — quarantining the key for
+    /// `quarantine_cooldown`, which doubles (capped at `MAX_QUARANTINE`)
+    /// each time it's re-applied — once `FAILURE_THRESHOLD` consecutive
+    /// failures pile up, or immediately if a `Probing` key fails its trial
+    /// request.
+    pub fn report_failure(&self, key: &str, failure: KeyFailure) {
+        let mut keys = self.keys.lock().unwrap();
+        if let Some(api_key) = keys.iter_mut().find(|k| k.key == key) {
+            api_key.consecutive_failures += 1;
+            let was_probing = matches!(api_key.health, KeyHealth::Probing);
+            if was_probing || api_key.consecutive_failures >= FAILURE_THRESHOLD {
+                let cooldown = api_key.quarantine_cooldown;
+                api_key.health = KeyHealth::Quarantined {
+                    until: Utc::now() + chrono::Duration::from_std(cooldown).unwrap(),
+                };
+                api_key.quarantine_cooldown = (cooldown * 2).min(MAX_QUARANTINE);
+                log::warn!(
+                    "Quarantining API key ending in ...{} for {:?} after a {:?} failure{}",
+                    api_key.key.chars().rev().take(4).collect::<String>(),
+                    cooldown,
+                    failure,
+                    if was_probing { " (probe failed)" } else { "" }
+                );
+            }
+        }
+        self.persist(&keys);
+    }
+
+    /// Returns `(healthy, cooling_down, quarantined)` key counts for
+    /// `/stats`-style reporting. A key counts as cooling down while its 429
+    /// backoff from [`Self::report_rate_limited`] hasn't elapsed yet, and as
+    /// quarantined while the circuit breaker has it in
+    /// [`KeyHealth::Quarantined`] (sustained 401/5xx failures) — neither
+    /// bucket counts toward `healthy`, so a dead pool isn't reported as
+    /// all-healthy.
+    pub fn key_health(&self) -> (usize, usize, usize) {
+        let keys_guard = self.keys.lock().unwrap();
+        let now = Utc::now();
+        let cooling_down = keys_guard
+            .iter()
+            .filter(|k| k.disabled_until.is_some_and(|until| until > now))
+            .count();
+        let quarantined = keys_guard
+            .iter()
+            .filter(|k| matches!(k.health, KeyHealth::Quarantined { until } if until > now))
+            .count();
+        (
+            keys_guard.len().saturating_sub(cooling_down + quarantined),
+            cooling_down,
+            quarantined,
+        )
+    }
+
+    /// Per-key request counts and remaining rpm/rpd budget, for `/metrics`.
+    /// Keys are identified by `index` (their position in the configured key
+    /// list) rather than the key itself, so this is safe to expose on a
+    /// scraped endpoint.
+    pub fn key_metrics(&self) -> Vec<KeyMetrics> {
+        let keys_guard = self.keys.lock().unwrap();
+        let now = Utc::now();
+        let one_minute_ago = now - chrono::Duration::minutes(1);
+
+        keys_guard
+            .iter()
+            .enumerate()
+            .map(|(index, api_key)| {
+                let requests_last_minute = api_key
+                    .requests
+                    .iter()
+                    .filter(|&&t| t > one_minute_ago)
+                    .count() as u32;
+                let requests_last_day = api_key.requests.len() as u32;
+                KeyMetrics {
+                    index,
+                    requests_last_minute,
+                    requests_last_day,
+                    rpm_remaining: self.rpm_limit.saturating_sub(requests_last_minute),
+                    rpd_remaining: self.rpd_limit.saturating_sub(requests_last_day),
+                    cooling_down: api_key.disabled_until.is_some_and(|until| until > now),
+                    quarantined: matches!(api_key.health, KeyHealth::Quarantined { until } if until > now),
+                }
+            })
+            .collect()
+    }
+
+    /// Resets `key`'s backoff back to the base cooldown and, if it was
+    /// `Probing`, promotes it back to `Healthy` after it completes a call
+    /// successfully. A single transient burst of 429s or failures shouldn't
+    /// keep inflating the key's cooldown/quarantine indefinitely.
+    pub fn report_success(&self, key: &str) {
+        let mut keys = self.keys.lock().unwrap();
+        if let Some(api_key) = keys.iter_mut().find(|k| k.key == key) {
+            api_key.next_cooldown = BASE_COOLDOWN;
+            api_key.consecutive_failures = 0;
+            if matches!(api_key.health, KeyHealth::Probing) {
+                log::info!(
+                    "API key ending in ...{} passed its health probe; marking healthy.",
+                    api_key.key.chars().rev().take(4).collect::<String>()
+                );
+            }
+            api_key.health = KeyHealth::Healthy;
+            api_key.quarantine_cooldown = BASE_QUARANTINE;
+        }
+        self.persist(&keys);
     }
 }
@@ -0,0 +1,2 @@
+pub mod generation;
+pub mod key_manager;
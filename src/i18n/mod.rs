@@ -0,0 +1,322 @@
+//! src/i18n/mod.rs
+//!
+//! Localization for user-facing bot replies. Every reply is identified by a
+//! [`MessageId`] (optionally carrying interpolation parameters) and rendered
+//! for a [`Locale`] via [`t`], instead of handlers hard-coding a single
+//! language. The active locale is per-chat (`AppState::chat_locales`,
+//! settable at runtime with `/lang`), falling back to
+//! `config.i18n.default_locale` for chats that haven't picked one.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A supported UI language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+pub enum Locale {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Locale {
+    /// Parses a `/lang` argument or config value (`zh`, `zh-CN`, `en`, ...).
+    pub fn parse(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "zh" | "zh-cn" | "zh_cn" => Some(Self::Zh),
+            "en" | "en-us" | "en_us" => Some(Self::En),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            Self::Zh => "zh",
+            Self::En => "en",
+        };
+        write!(f, "{code}")
+    }
+}
+
+/// Identifies a user-facing message, carrying whatever parameters it needs
+/// to interpolate into the rendered string.
+pub enum MessageId {
+    NoPermissionPrivate,
+    AdminOnly,
+    QaInitializing,
+    Welcome,
+    AddQaNeedsReply,
+    AddQaNeedsText,
+    AddQaOcrFailed { error: String },
+    AddQaCapturedHeader,
+    AddQaFollowUp,
+    QaUpdated,
+    ListQaEmpty,
+    ListQaPrompt,
+    SearchQaEmptyKeyword,
+    SearchQaNoMatches { keyword: String },
+    SearchQaFound,
+    SnoozeAck { minutes: u64 },
+    ResumeAck,
+    ResumeNotSnoozed,
+    IngestNeedsReply,
+    IngestNeedsText,
+    IngestInProgress,
+    IngestDone { source: String, count: usize },
+    IngestFailed { error: String },
+    AnswerNeedsReply,
+    AnswerNeedsText,
+    AnswerNotFound,
+    AnswerSearchError,
+    LangUsage,
+    LangSet { locale: Locale },
+    LangUnknown { code: String },
+    Stats {
+        qa_count: usize,
+        embedding_model: String,
+        embedding_ndims: usize,
+        healthy_keys: usize,
+        cooling_down_keys: usize,
+        quarantined_keys: usize,
+        snoozed: bool,
+        hits: u64,
+        misses: u64,
+    },
+    ModerationWarning,
+    ModerationMuted { minutes: u64 },
+    PardonNeedsReply,
+    PardonNotMuted,
+    PardonAck,
+    ReloadDone {
+        added: usize,
+        updated: usize,
+        removed: usize,
+        unchanged: usize,
+    },
+    ReloadFailed {
+        error: String,
+    },
+    ContextCleared,
+}
+
+/// Renders `id` as a string for `locale`.
+pub fn t(locale: Locale, id: &MessageId) -> String {
+    use Locale::{En, Zh};
+    use MessageId::*;
+
+    match (id, locale) {
+        (NoPermissionPrivate, Zh) => "您无权在私聊中使用命令。".to_string(),
+        (NoPermissionPrivate, En) => {
+            "You're not authorized to use commands in a private chat.".to_string()
+        }
+
+        (AdminOnly, Zh) => "只有管理员才能使用此命令。".to_string(),
+        (AdminOnly, En) => "Only administrators can use this command.".to_string(),
+
+        (QaInitializing, Zh) => "⌛️ 问答系统正在初始化，请稍后再试...".to_string(),
+        (QaInitializing, En) => "⌛️ The QA system is still initializing, please try again shortly...".to_string(),
+
+        (Welcome, Zh) => "您好！我已经准备好回答您的问题了。".to_string(),
+        (Welcome, En) => "Hello! I'm ready to answer your questions.".to_string(),
+
+        (AddQaNeedsReply, Zh) => "请通过回复您想设置为问题的消息来使用此命令。".to_string(),
+        (AddQaNeedsReply, En) => {
+            "Reply to the message you want to use as the question to use this command.".to_string()
+        }
+
+        (AddQaNeedsText, Zh) => "被回复的消息必须包含文本才能用作问题。".to_string(),
+        (AddQaNeedsText, En) => {
+            "The replied-to message must contain text to be used as a question.".to_string()
+        }
+
+        (AddQaOcrFailed { error }, Zh) => format!("图片文字识别失败：{error}"),
+        (AddQaOcrFailed { error }, En) => format!("Failed to recognize text in the image: {error}"),
+
+        (AddQaCapturedHeader, Zh) => "❓ 问题已捕获\n\n".to_string(),
+        (AddQaCapturedHeader, En) => "❓ Question captured\n\n".to_string(),
+
+        (AddQaFollowUp, Zh) => "\n\n管理员现在必须回复此消息以提供相应答案。".to_string(),
+        (AddQaFollowUp, En) => "\n\nAn admin must now reply to this message with the answer.".to_string(),
+
+        (QaUpdated, Zh) => "✅ 问答对已成功更新！".to_string(),
+        (QaUpdated, En) => "✅ QA pair updated successfully!".to_string(),
+
+        (ListQaEmpty, Zh) => "未找到任何问答对。".to_string(),
+        (ListQaEmpty, En) => "No QA pairs found.".to_string(),
+
+        (ListQaPrompt, Zh) => "所有问答对。点击进行管理：".to_string(),
+        (ListQaPrompt, En) => "All QA pairs. Tap one to manage it:".to_string(),
+
+        (SearchQaEmptyKeyword, Zh) => "请输入要搜索的关键字。".to_string(),
+        (SearchQaEmptyKeyword, En) => "Please enter a keyword to search for.".to_string(),
+
+        (SearchQaNoMatches { keyword }, Zh) => format!("未找到与“{keyword}”相关的匹配项。"),
+        (SearchQaNoMatches { keyword }, En) => format!("No matches found for \"{keyword}\"."),
+
+        (SearchQaFound, Zh) => "找到以下问答对。点击进行管理：".to_string(),
+        (SearchQaFound, En) => "Found the following QA pairs. Tap one to manage it:".to_string(),
+
+        (SnoozeAck { minutes }, Zh) => format!("好的，我将暂停自动回复 {minutes} 分钟。"),
+        (SnoozeAck { minutes }, En) => {
+            format!("OK, I'll pause automatic replies for {minutes} minutes.")
+        }
+
+        (ResumeAck, Zh) => "好的，自动回复已恢复。".to_string(),
+        (ResumeAck, En) => "OK, automatic replies have been resumed.".to_string(),
+
+        (ResumeNotSnoozed, Zh) => "我当前并未处于暂停状态。".to_string(),
+        (ResumeNotSnoozed, En) => "I'm not currently snoozed.".to_string(),
+
+        (IngestNeedsReply, Zh) => {
+            "请通过回复您想导入的长文本消息来使用此命令，并附上来源名称，如：/ingest 产品手册".to_string()
+        }
+        (IngestNeedsReply, En) => {
+            "Reply to the long text message you want to import, with a source name, e.g.: /ingest product-manual".to_string()
+        }
+
+        (IngestNeedsText, Zh) => "被回复的消息必须包含文本才能导入。".to_string(),
+        (IngestNeedsText, En) => "The replied-to message must contain text to be imported.".to_string(),
+
+        (IngestInProgress, Zh) => "⌛️ 正在分块并生成词向量，请稍候...".to_string(),
+        (IngestInProgress, En) => "⌛️ Chunking and generating embeddings, please wait...".to_string(),
+
+        (IngestDone { source, count }, Zh) => {
+            format!("✅ 已从“{source}”导入完成，新增 {count} 个词向量（其余复用缓存）。")
+        }
+        (IngestDone { source, count }, En) => format!(
+            "✅ Finished importing from \"{source}\": {count} new embeddings generated (the rest reused the cache)."
+        ),
+
+        (IngestFailed { error }, Zh) => format!("导入失败：{error}"),
+        (IngestFailed { error }, En) => format!("Import failed: {error}"),
+
+        (AnswerNeedsReply, Zh) => "请通过回复您想提问的消息来使用此命令。".to_string(),
+        (AnswerNeedsReply, En) => {
+            "Reply to the message you want answered to use this command.".to_string()
+        }
+
+        (AnswerNeedsText, Zh) => "被回复的消息必须包含文本。".to_string(),
+        (AnswerNeedsText, En) => "The replied-to message must contain text.".to_string(),
+
+        (AnswerNotFound, Zh) => "抱歉，我找不到该问题的答案。".to_string(),
+        (AnswerNotFound, En) => "Sorry, I couldn't find an answer to that question.".to_string(),
+
+        (AnswerSearchError, Zh) => "搜索答案时发生错误。".to_string(),
+        (AnswerSearchError, En) => "An error occurred while searching for an answer.".to_string(),
+
+        (LangUsage, Zh) => "用法：/lang <zh|en>".to_string(),
+        (LangUsage, En) => "Usage: /lang <zh|en>".to_string(),
+
+        (LangSet { locale }, Zh) => format!("✅ 本群语言已切换为 {locale}。"),
+        (LangSet { locale }, En) => format!("✅ This chat's language is now {locale}."),
+
+        (LangUnknown { code }, Zh) => format!("不支持的语言代码：“{code}”。可选：zh、en。"),
+        (LangUnknown { code }, En) => format!("Unsupported language code: \"{code}\". Options: zh, en."),
+
+        (
+            Stats {
+                qa_count,
+                embedding_model,
+                embedding_ndims,
+                healthy_keys,
+                cooling_down_keys,
+                quarantined_keys,
+                snoozed,
+                hits,
+                misses,
+            },
+            Zh,
+        ) => {
+            let total = hits + misses;
+            let rate = if total > 0 {
+                *hits as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            };
+            format!(
+                "📊 系统状态\n\n问答库：{qa_count} 条\n词向量模型：{embedding_model}（{embedding_ndims} 维）\nAPI Key：{healthy_keys} 个可用，{cooling_down_keys} 个冷却中，{quarantined_keys} 个已隔离\n自动回复：{}\n命中率：{hits}/{total}（{rate:.1}%）",
+                if *snoozed { "已暂停" } else { "运行中" }
+            )
+        }
+        (
+            Stats {
+                qa_count,
+                embedding_model,
+                embedding_ndims,
+                healthy_keys,
+                cooling_down_keys,
+                quarantined_keys,
+                snoozed,
+                hits,
+                misses,
+            },
+            En,
+        ) => {
+            let total = hits + misses;
+            let rate = if total > 0 {
+                *hits as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            };
+            format!(
+                "📊 System status\n\nQA pairs: {qa_count}\nEmbedding model: {embedding_model} ({embedding_ndims} dims)\nAPI keys: {healthy_keys} healthy, {cooling_down_keys} cooling down, {quarantined_keys} quarantined\nAuto-reply: {}\nHit rate: {hits}/{total} ({rate:.1}%)",
+                if *snoozed { "paused" } else { "running" }
+            )
+        }
+
+        (ModerationWarning, Zh) => {
+            "⚠️ 您发消息的频率过高，请放慢一些，否则将被暂时禁言。".to_string()
+        }
+        (ModerationWarning, En) => {
+            "⚠️ You're sending messages too quickly. Slow down or you'll be muted temporarily.".to_string()
+        }
+
+        (ModerationMuted { minutes }, Zh) => {
+            format!("🔇 由于消息频率过高，您已被禁言 {minutes} 分钟。")
+        }
+        (ModerationMuted { minutes }, En) => {
+            format!("🔇 You've been muted for {minutes} minutes for sending messages too quickly.")
+        }
+
+        (PardonNeedsReply, Zh) => "请通过回复被禁言用户的消息来使用此命令。".to_string(),
+        (PardonNeedsReply, En) => {
+            "Reply to a message from the muted user to use this command.".to_string()
+        }
+
+        (PardonNotMuted, Zh) => "该用户当前并未被本机器人禁言。".to_string(),
+        (PardonNotMuted, En) => "That user isn't currently muted by this bot.".to_string(),
+
+        (PardonAck, Zh) => "✅ 已解除该用户的禁言。".to_string(),
+        (PardonAck, En) => "✅ The user's mute has been lifted.".to_string(),
+
+        (
+            ReloadDone {
+                added,
+                updated,
+                removed,
+                unchanged,
+            },
+            Zh,
+        ) => format!(
+            "✅ 配置与问答库已重新加载。\n新增 {added} 条，更新 {updated} 条，删除 {removed} 条，未变化 {unchanged} 条。"
+        ),
+        (
+            ReloadDone {
+                added,
+                updated,
+                removed,
+                unchanged,
+            },
+            En,
+        ) => format!(
+            "✅ Configuration and QA data reloaded.\nAdded {added}, updated {updated}, removed {removed}, unchanged {unchanged}."
+        ),
+
+        (ReloadFailed { error }, Zh) => format!("❌ 重新加载失败：{error}"),
+        (ReloadFailed { error }, En) => format!("❌ Reload failed: {error}"),
+
+        (ContextCleared, Zh) => "✅ 已清空本对话的上下文记录。".to_string(),
+        (ContextCleared, En) => "✅ This chat's conversation context has been cleared.".to_string(),
+    }
+}
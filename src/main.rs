@@ -2,10 +2,11 @@ use anyhow::Context as _;
 use bot::{
     callbacks::callback_handler,
     commands::{Command, command_handler},
+    hooks::{AccessGateHook, Hook, HookRegistry, RateLimitHook, RequestLoggingHook},
     message::message_handler,
     state::AppState,
 };
-use config::load_user_config;
+use config::{config_file_path, load_user_config};
 use gemini::key_manager::GeminiKeyManager;
 use qa::QAService;
 use std::sync::Arc;
@@ -15,6 +16,9 @@ use tokio::sync::Mutex;
 mod bot;
 mod config;
 mod gemini;
+mod i18n;
+mod metrics;
+mod ocr;
 mod qa;
 
 #[tokio::main]
@@ -29,14 +33,70 @@ async fn main() -> Result<(), anyhow::Error> {
         config.embedding.api_keys.clone(),
         config.embedding.rpm,
         config.embedding.rpd,
+        &config.cache.dir,
     ));
-    let app_state = Arc::new(Mutex::new(AppState::new()));
+    let app_state = Arc::new(Mutex::new(AppState::new(&config)));
     // Create the new QAService, wrapped for sharing across threads
     let qa_service = Arc::new(Mutex::new(QAService::new(
         config.clone(),
         key_manager.clone(),
     )));
 
+    // --- Hook Registry Setup ---
+    // Order matters: logging first so rejected updates are still audited,
+    // then the coarse access gate, then the rate limiter.
+    let mut hooks: Vec<Arc<dyn Hook>> = vec![Arc::new(RequestLoggingHook), Arc::new(AccessGateHook)];
+    if config.rate_limit.enabled {
+        hooks.push(Arc::new(RateLimitHook::new(
+            config.rate_limit.max_requests,
+            config.rate_limit.window_secs,
+        )));
+    }
+    let hook_registry = Arc::new(HookRegistry::new(hooks));
+
+    // --- Peer-Gossip Setup ---
+    // A no-op when `config.gossip.enabled` is false, so single-replica
+    // deployments are unaffected.
+    let gossip = qa::gossip::start(&config, qa_service.clone())
+        .await
+        .context("Failed to start gossip subsystem")?;
+    qa_service.lock().await.set_gossip(gossip);
+
+    // --- Config/QA Hot-Reload Watcher ---
+    // Polls the config file and QA JSON file for changes so operators can
+    // tune settings or edit the QA corpus without restarting the bot.
+    qa::watcher::start(
+        config_file_path().context("Failed to resolve config file path")?,
+        qa_service.clone(),
+    );
+
+    // --- Admin HTTP Server (metrics + health) ---
+    // A no-op when `config.admin.enabled` is false.
+    metrics::start(
+        config.clone(),
+        qa_service.clone(),
+        app_state.clone(),
+        key_manager.clone(),
+    )
+    .await
+    .context("Failed to start admin HTTP server")?;
+
+    // --- Dialogue Expiry Sweep ---
+    // Periodically drops add/edit-QA conversations older than
+    // `config.dialogue.ttl_secs` (a no-op for the Redis backend).
+    let app_state_sweep = app_state.clone();
+    let dialogue_ttl_secs = config.dialogue.ttl_secs;
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            let state_guard = app_state_sweep.lock().await;
+            if let Err(e) = state_guard.dialogue_store.prune_expired(dialogue_ttl_secs).await {
+                log::warn!("Failed to prune expired dialogues: {:?}", e);
+            }
+        }
+    });
+
     // --- Asynchronous QA Data Loading ---
     let qa_service_clone = qa_service.clone();
     let app_state_clone = app_state.clone();
@@ -79,7 +139,7 @@ async fn main() -> Result<(), anyhow::Error> {
 
     Dispatcher::builder(bot, handler)
         // Pass the new qa_service instead of the raw system/config/key_manager
-        .dependencies(dptree::deps![qa_service, app_state])
+        .dependencies(dptree::deps![qa_service, app_state, hook_registry])
         .enable_ctrlc_handler()
         .build()
         .dispatch()
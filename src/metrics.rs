@@ -0,0 +1,200 @@
+//! src/metrics.rs
+//!
+//! A minimal admin HTTP server exposing `/metrics` (Prometheus text
+//! exposition format) and `/healthz` (liveness probe) on `config.admin.bind_addr`,
+//! so operators can scrape the bot's internal state and wire alerting without
+//! reading logs. Hand-rolls just enough HTTP to read a request line and write
+//! a fixed response, rather than pulling in a full HTTP framework for two
+//! read-only, argument-free endpoints.
+
+use crate::bot::state::AppState;
+use crate::config::Config;
+use crate::gemini::key_manager::GeminiKeyManager;
+use crate::qa::service::QAService;
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+/// Starts the admin HTTP server if `config.admin.enabled`, spawning an
+/// accept loop that serves `/metrics` and `/healthz`. A no-op when disabled,
+/// so existing deployments are unaffected.
+pub async fn start(
+    config: Arc<Config>,
+    qa_service: Arc<Mutex<QAService>>,
+    app_state: Arc<Mutex<AppState>>,
+    key_manager: Arc<GeminiKeyManager>,
+) -> Result<()> {
+    if !config.admin.enabled {
+        return Ok(());
+    }
+
+    let listener = TcpListener::bind(&config.admin.bind_addr)
+        .await
+        .with_context(|| format!("Failed to bind admin HTTP server on {}", config.admin.bind_addr))?;
+    log::info!("Admin HTTP server listening on {}", config.admin.bind_addr);
+
+    tokio::spawn(accept_loop(listener, qa_service, app_state, key_manager));
+    Ok(())
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    qa_service: Arc<Mutex<QAService>>,
+    app_state: Arc<Mutex<AppState>>,
+    key_manager: Arc<GeminiKeyManager>,
+) {
+    loop {
+        let (stream, _addr) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                log::warn!("Admin HTTP server accept error: {}", e);
+                continue;
+            }
+        };
+        tokio::spawn(handle_connection(
+            stream,
+            qa_service.clone(),
+            app_state.clone(),
+            key_manager.clone(),
+        ));
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    qa_service: Arc<Mutex<QAService>>,
+    app_state: Arc<Mutex<AppState>>,
+    key_manager: Arc<GeminiKeyManager>,
+) {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    if let Err(e) = reader.read_line(&mut request_line).await {
+        log::warn!("Failed to read admin HTTP request line: {}", e);
+        return;
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+
+    let (status, content_type, body) = match path {
+        "/metrics" => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            render_metrics(&qa_service, &app_state, &key_manager).await,
+        ),
+        "/healthz" => {
+            if app_state.lock().await.is_qa_ready {
+                ("200 OK", "text/plain", "ok\n".to_string())
+            } else {
+                (
+                    "503 Service Unavailable",
+                    "text/plain",
+                    "not ready\n".to_string(),
+                )
+            }
+        }
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    let mut stream = reader.into_inner();
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        log::warn!("Failed to write admin HTTP response: {}", e);
+    }
+}
+
+/// Renders every tracked gauge/counter in the Prometheus text exposition format.
+async fn render_metrics(
+    qa_service: &Arc<Mutex<QAService>>,
+    app_state: &Arc<Mutex<AppState>>,
+    key_manager: &Arc<GeminiKeyManager>,
+) -> String {
+    let service = qa_service.lock().await;
+    let state = app_state.lock().await;
+
+    let mut out = String::new();
+    push_gauge(
+        &mut out,
+        "qa_items_total",
+        "Number of QA items currently loaded.",
+        service.qa_data_len() as f64,
+    );
+    push_gauge(
+        &mut out,
+        "qa_embeddings_total",
+        "Number of question embeddings currently loaded.",
+        service.question_embeddings_len() as f64,
+    );
+    push_gauge(
+        &mut out,
+        "qa_ready",
+        "1 if the QA system has finished its initial load, else 0.",
+        if state.is_qa_ready { 1.0 } else { 0.0 },
+    );
+    push_counter(
+        &mut out,
+        "qa_match_hits_total",
+        "Auto-replies that found a matching answer.",
+        state.auto_reply_hits as f64,
+    );
+    push_counter(
+        &mut out,
+        "qa_match_misses_total",
+        "Auto-replies that found no matching answer.",
+        state.auto_reply_misses as f64,
+    );
+    push_counter(
+        &mut out,
+        "qa_embedding_errors_total",
+        "Errors returned while looking up a matching answer.",
+        state.embedding_errors as f64,
+    );
+
+    push_per_key_metrics(&mut out, key_manager);
+
+    out
+}
+
+fn push_per_key_metrics(out: &mut String, key_manager: &GeminiKeyManager) {
+    push_help_and_type(out, "gemini_key_requests_last_minute", "Requests sent with this Gemini API key in the last minute.", "gauge");
+    push_help_and_type(out, "gemini_key_requests_last_day", "Requests sent with this Gemini API key in the last 24 hours.", "gauge");
+    push_help_and_type(out, "gemini_key_rpm_remaining", "Remaining per-minute request budget for this Gemini API key.", "gauge");
+    push_help_and_type(out, "gemini_key_rpd_remaining", "Remaining per-day request budget for this Gemini API key.", "gauge");
+    push_help_and_type(out, "gemini_key_cooling_down", "1 if this Gemini API key is currently cooling down from a rate limit, else 0.", "gauge");
+    push_help_and_type(out, "gemini_key_quarantined", "1 if this Gemini API key is currently quarantined by the circuit breaker, else 0.", "gauge");
+
+    for key in key_manager.key_metrics() {
+        let label = format!("{{index=\"{}\"}}", key.index);
+        out.push_str(&format!("gemini_key_requests_last_minute{label} {}\n", key.requests_last_minute));
+        out.push_str(&format!("gemini_key_requests_last_day{label} {}\n", key.requests_last_day));
+        out.push_str(&format!("gemini_key_rpm_remaining{label} {}\n", key.rpm_remaining));
+        out.push_str(&format!("gemini_key_rpd_remaining{label} {}\n", key.rpd_remaining));
+        out.push_str(&format!(
+            "gemini_key_cooling_down{label} {}\n",
+            if key.cooling_down { 1 } else { 0 }
+        ));
+        out.push_str(&format!(
+            "gemini_key_quarantined{label} {}\n",
+            if key.quarantined { 1 } else { 0 }
+        ));
+    }
+}
+
+fn push_help_and_type(out: &mut String, name: &str, help: &str, metric_type: &str) {
+    out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {metric_type}\n"));
+}
+
+fn push_gauge(out: &mut String, name: &str, help: &str, value: f64) {
+    push_help_and_type(out, name, help, "gauge");
+    out.push_str(&format!("{name} {value}\n"));
+}
+
+fn push_counter(out: &mut String, name: &str, help: &str, value: f64) {
+    push_help_and_type(out, name, help, "counter");
+    out.push_str(&format!("{name} {value}\n"));
+}
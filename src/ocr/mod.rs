@@ -0,0 +1,93 @@
+//! src/ocr/mod.rs
+//!
+//! OCR support so image messages (screenshots, photos) can be used as
+//! questions and answers, not just plain text. Wraps Tesseract via
+//! `leptess`. Disabled by default (`config.ocr.enabled = false`) so
+//! deployments without the Tesseract data files installed are unaffected.
+
+use crate::config::Config;
+use anyhow::{Context, Result, anyhow};
+use teloxide::net::Download;
+use teloxide::prelude::*;
+use teloxide::types::Message;
+
+/// Returns the text to treat as `message`'s content: `message.text()` if
+/// present, otherwise OCR over the largest photo (or an image sent as a
+/// document) using `config.ocr`'s configured languages. Returns `Ok(None)`
+/// if there's no text and no recognizable image, or if OCR is disabled.
+/// Returns `Err` if an image was found but no text could be recognized in
+/// it, so callers can surface a clear error instead of silently ignoring
+/// the message.
+pub async fn extract_text(bot: &Bot, message: &Message, config: &Config) -> Result<Option<String>> {
+    if let Some(text) = message.text() {
+        return Ok(Some(text.to_string()));
+    }
+    if !config.ocr.enabled {
+        return Ok(None);
+    }
+
+    let Some(file_id) = largest_photo_file_id(message).or_else(|| document_image_file_id(message))
+    else {
+        return Ok(None);
+    };
+
+    let image_bytes = download_file(bot, &file_id).await?;
+    let text = recognize_text(image_bytes, config.ocr.languages.clone()).await?;
+    if text.trim().is_empty() {
+        return Err(anyhow!("未能在图片中识别出任何文字。"));
+    }
+    Ok(Some(text))
+}
+
+fn largest_photo_file_id(message: &Message) -> Option<String> {
+    message
+        .photo()?
+        .iter()
+        .max_by_key(|photo| photo.width * photo.height)
+        .map(|photo| photo.file.id.clone())
+}
+
+fn document_image_file_id(message: &Message) -> Option<String> {
+    let document = message.document()?;
+    let is_image = document
+        .mime_type
+        .as_ref()
+        .is_some_and(|mime| mime.as_ref().starts_with("image/"));
+    is_image.then(|| document.file.id.clone())
+}
+
+async fn download_file(bot: &Bot, file_id: &str) -> Result<Vec<u8>> {
+    let file = bot
+        .get_file(file_id)
+        .await
+        .context("Failed to get file info from Telegram")?;
+    let mut buf = Vec::new();
+    bot.download_file(&file.path, &mut buf)
+        .await
+        .context("Failed to download file from Telegram")?;
+    Ok(buf)
+}
+
+/// Runs Tesseract OCR over `image_bytes` using `languages` (joined with `+`,
+/// Tesseract's multi-language syntax). Offloaded to a blocking thread since
+/// `leptess` is synchronous and CPU-bound.
+async fn recognize_text(image_bytes: Vec<u8>, languages: Vec<String>) -> Result<String> {
+    tokio::task::spawn_blocking(move || -> Result<String> {
+        let lang = languages.join("+");
+        let mut recognizer = leptess::LepTess::new(None, &lang).map_err(|e| {
+            anyhow!(
+                "Failed to initialize Tesseract for languages '{}': {}",
+                lang,
+                e
+            )
+        })?;
+        recognizer
+            .set_image_from_mem(&image_bytes)
+            .map_err(|e| anyhow!("Failed to load image for OCR: {}", e))?;
+        recognizer
+            .get_utf8_text()
+            .map_err(|e| anyhow!("OCR recognition failed: {}", e))
+    })
+    .await
+    .context("OCR task panicked")?
+}
@@ -1,4 +1,8 @@
-use crate::{config::Config, gemini::key_manager::GeminiKeyManager};
+use crate::{
+    config::Config,
+    gemini::key_manager::{GeminiKeyManager, KeyFailure},
+    qa::types::FormattedText,
+};
 use anyhow::{Result, anyhow};
 use rig::{
     client::EmbeddingsClient, embeddings::builder::EmbeddingsBuilder,
@@ -25,6 +29,20 @@ async fn generate_single_embedding(api_key: &str, config: &Config, text: &str) -
     Ok(embedding)
 }
 
+/// Embeds a single QA item's question, for incremental `add_qa`/`update_qa`
+/// mutations that only need to (re)embed the one pair that changed instead
+/// of reloading and re-embedding the whole corpus through
+/// [`super::service::QAService::load_and_embed_all`]. A thin, named wrapper
+/// around [`generate_embedding_with_retry`] so call sites read as "embed
+/// this one item" rather than a bare embedding call.
+pub async fn embed_one(
+    config: &Config,
+    key_manager: &Arc<GeminiKeyManager>,
+    question: &FormattedText,
+) -> Result<Vec<f64>> {
+    generate_embedding_with_retry(config, key_manager, &question.text).await
+}
+
 /// 生成单个词向量，包含重试和 API Key 管理逻辑
 pub async fn generate_embedding_with_retry(
     config: &Config,
@@ -46,22 +64,41 @@ pub async fn generate_embedding_with_retry(
         let api_key = match key_manager.get_key() {
             Ok(key) => key,
             Err(e) => {
-                log::error!("Could not get API key: {}. Retrying in 60s.", e);
-                sleep(Duration::from_secs(60)).await;
+                let wait = key_manager
+                    .min_cooldown_remaining()
+                    .unwrap_or(Duration::from_secs(60));
+                log::error!("Could not get API key: {}. Retrying in {:?}.", e, wait);
+                sleep(wait).await;
                 continue;
             }
         };
 
         match generate_single_embedding(&api_key, config, text).await {
-            Ok(embedding) => return Ok(embedding),
+            Ok(embedding) => {
+                key_manager.report_success(&api_key);
+                return Ok(embedding);
+            }
             Err(e) => {
                 let error_string = e.to_string().to_lowercase();
                 if error_string.contains("429")
                     || error_string.contains("resource has been exhausted")
                 {
-                    log::warn!("API key rate-limited. Disabling it. Error: {}", e);
-                    key_manager.disable_key(&api_key);
+                    log::warn!("API key rate-limited. Cooling it down. Error: {}", e);
+                    key_manager.report_rate_limited(&api_key);
                     continue; // Immediately try with the next key
+                } else if error_string.contains("401") || error_string.contains("403") {
+                    log::warn!("API key unauthorized. Error: {}", e);
+                    key_manager.report_failure(&api_key, KeyFailure::Unauthorized);
+                    continue;
+                } else if error_string.contains("500")
+                    || error_string.contains("502")
+                    || error_string.contains("503")
+                    || error_string.contains("504")
+                {
+                    log::warn!("Gemini server error. Error: {}", e);
+                    key_manager.report_failure(&api_key, KeyFailure::ServerError);
+                    sleep(Duration::from_secs(5)).await;
+                    continue;
                 } else {
                     log::error!("Failed to generate embedding: {}. Retrying in 5s...", e);
                     sleep(Duration::from_secs(5)).await;
@@ -0,0 +1,247 @@
+//! src/qa/gossip.rs
+//!
+//! Lightweight UDP peer-gossip so QA mutations made on one bot replica reach
+//! the others in near-real-time, instead of the others only picking them up
+//! on their next restart and `load_and_embed_all`. Each node tags its own
+//! mutations with a `(counter, node_id)` logical timestamp; receivers
+//! de-duplicate by that pair and resolve conflicting edits to the same
+//! question hash with last-writer-wins on the timestamp.
+//!
+//! This is intentionally best-effort: UDP datagrams can be dropped or
+//! reordered, and per-node counters aren't synchronized into a true vector
+//! clock. That's an acceptable tradeoff for keeping replicas roughly in
+//! sync; a replica that misses an event will still get the final state next
+//! time it restarts and reloads from the shared [`super::store::Store`].
+
+use super::{service::QAService, types::FormattedText};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tokio::sync::Mutex;
+
+/// What happened to the QA item addressed by a [`ChangeEvent`]'s question hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GossipOp {
+    /// Insert a brand-new item, or replace the item currently stored under
+    /// the event's question hash (used for updates, where the hash is the
+    /// *old* question's hash and the new content may hash differently).
+    Upsert {
+        question: FormattedText,
+        answer: FormattedText,
+        source: Option<String>,
+        /// Carried along so receivers don't need to call out to Gemini just
+        /// to catch up with a peer's edit.
+        embedding: Vec<f64>,
+    },
+    /// Remove the item stored under the event's question hash.
+    Delete,
+}
+
+/// A single QA mutation plus the logical timestamp used for de-duplication
+/// and last-writer-wins ordering across the gossip mesh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub node_id: u64,
+    pub counter: u64,
+    pub question_hash: String,
+    pub op: GossipOp,
+}
+
+/// Wire envelope: the shared auth token travels alongside the event so the
+/// receive loop can drop unauthenticated datagrams before touching `QAService`.
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    token: String,
+    event: ChangeEvent,
+}
+
+/// Shared gossip state: outbound broadcasting and the per-node counter used
+/// to tag this replica's own mutations.
+pub struct GossipHandle {
+    socket: UdpSocket,
+    peers: Vec<SocketAddr>,
+    auth_token: String,
+    node_id: u64,
+    counter: AtomicU64,
+}
+
+impl GossipHandle {
+    /// Tags `op` for `question_hash` with a fresh logical timestamp and
+    /// sends it to every configured peer, returning the `(counter, node_id)`
+    /// timestamp used so the caller can record it for its own conflict
+    /// resolution.
+    pub async fn broadcast(&self, question_hash: String, op: GossipOp) -> (u64, u64) {
+        let counter = self.counter.fetch_add(1, Ordering::SeqCst);
+        let envelope = Envelope {
+            token: self.auth_token.clone(),
+            event: ChangeEvent {
+                node_id: self.node_id,
+                counter,
+                question_hash,
+                op,
+            },
+        };
+
+        match serde_json::to_vec(&envelope) {
+            Ok(payload) => {
+                for peer in &self.peers {
+                    if let Err(e) = self.socket.send_to(&payload, peer).await {
+                        log::warn!("Failed to send gossip event to {}: {}", peer, e);
+                    }
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize gossip event: {}", e),
+        }
+
+        (counter, self.node_id)
+    }
+}
+
+/// De-duplicates `(node_id, counter)` pairs seen within a TTL window so a
+/// datagram re-delivered by the network isn't applied twice.
+struct DedupCache {
+    seen: HashMap<(u64, u64), Instant>,
+    ttl: Duration,
+}
+
+impl DedupCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            seen: HashMap::new(),
+            ttl,
+        }
+    }
+
+    /// Returns `true` the first time `key` is observed within the TTL window,
+    /// `false` on a repeat. Sweeps expired entries along the way.
+    fn observe(&mut self, key: (u64, u64)) -> bool {
+        let now = Instant::now();
+        self.seen
+            .retain(|_, seen_at| now.duration_since(*seen_at) < self.ttl);
+        self.seen.insert(key, now).is_none()
+    }
+}
+
+/// A cheap, likely-unique id for this process, used to tell apart replicas'
+/// independent per-node counters. Doesn't need to be cryptographically
+/// random, just unlikely to collide between replicas started around the
+/// same time.
+fn generate_node_id() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    nanos ^ ((std::process::id() as u64) << 32)
+}
+
+/// Starts the gossip subsystem if `config.gossip.enabled`: binds the listen
+/// address, spawns a receive loop that applies incoming events to
+/// `qa_service`, and returns a handle the service can use to broadcast its
+/// own mutations. Returns `None` (and does nothing else) when gossip is
+/// disabled, so it's a no-op for existing single-replica deployments.
+pub async fn start(
+    config: &Config,
+    qa_service: Arc<Mutex<QAService>>,
+) -> Result<Option<Arc<GossipHandle>>> {
+    if !config.gossip.enabled {
+        return Ok(None);
+    }
+
+    let socket = UdpSocket::bind(&config.gossip.listen_addr)
+        .await
+        .with_context(|| format!("Failed to bind gossip socket on {}", config.gossip.listen_addr))?;
+
+    let peers = config
+        .gossip
+        .peers
+        .iter()
+        .filter_map(|addr| match addr.parse::<SocketAddr>() {
+            Ok(addr) => Some(addr),
+            Err(e) => {
+                log::warn!("Ignoring invalid gossip peer address '{}': {}", addr, e);
+                None
+            }
+        })
+        .collect();
+
+    let handle = Arc::new(GossipHandle {
+        socket,
+        peers,
+        auth_token: config.gossip.auth_token.clone(),
+        node_id: generate_node_id(),
+        counter: AtomicU64::new(0),
+    });
+
+    log::info!(
+        "Gossip subsystem listening on {} as node {:x}",
+        config.gossip.listen_addr,
+        handle.node_id
+    );
+
+    let dedup_ttl = Duration::from_secs(config.gossip.dedup_ttl_secs);
+    tokio::spawn(receive_loop(handle.clone(), qa_service, dedup_ttl));
+
+    Ok(Some(handle))
+}
+
+async fn receive_loop(
+    handle: Arc<GossipHandle>,
+    qa_service: Arc<Mutex<QAService>>,
+    dedup_ttl: Duration,
+) {
+    let mut dedup = DedupCache::new(dedup_ttl);
+    let mut buf = vec![0u8; 64 * 1024];
+
+    loop {
+        let len = match handle.socket.recv_from(&mut buf).await {
+            Ok((len, _src)) => len,
+            Err(e) => {
+                log::warn!("Gossip receive error: {}", e);
+                continue;
+            }
+        };
+
+        let envelope: Envelope = match serde_json::from_slice(&buf[..len]) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                log::warn!("Dropping malformed gossip datagram: {}", e);
+                continue;
+            }
+        };
+
+        if envelope.token != handle.auth_token {
+            log::warn!("Dropping gossip datagram with invalid auth token");
+            continue;
+        }
+
+        let event = envelope.event;
+        if event.node_id == handle.node_id {
+            // Our own broadcast, e.g. looped back by a misconfigured peer list.
+            continue;
+        }
+        if !dedup.observe((event.node_id, event.counter)) {
+            continue;
+        }
+
+        log::info!(
+            "Applying gossip event from node {:x} for question {}",
+            event.node_id,
+            short_hash(&event.question_hash)
+        );
+
+        let mut qa_service = qa_service.lock().await;
+        if let Err(e) = qa_service.apply_remote_event(event).await {
+            log::warn!("Failed to apply remote gossip event: {:?}", e);
+        }
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
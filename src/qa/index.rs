@@ -0,0 +1,481 @@
+//! src/qa/index.rs
+//!
+//! Pluggable nearest-neighbor search over question embeddings. `BruteForceIndex` is
+//! the original linear scan kept as the default/fallback; `HnswIndex` is an
+//! approximate alternative that scales far better once the QA corpus grows into
+//! the thousands. Both are addressed by a caller-assigned, position-independent
+//! `id` so that callers can keep mapping an id back to a question hash even as
+//! the underlying `qa_data`/`question_embeddings` vectors are compacted on delete.
+//! Callers building an index (see [`super::service::QAService::rebuild_index`])
+//! fall back to `BruteForceIndex` below `config.index.hnsw_min_items`, since an
+//! exact scan is already fast at small scale. `HnswIndex` is `Serialize`/
+//! `Deserialize` so it can be persisted to the cache dir (see
+//! `super::persistence::{save_hnsw_index, load_hnsw_index}`), and it reports
+//! `needs_rebuild` once tombstoned deletes pile up past a useful ratio.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::search::cosine_similarity;
+
+/// A nearest-neighbor index over a set of `(id, embedding)` pairs.
+///
+/// Implementations are free to choose how (or whether) `remove` physically
+/// drops state; callers only rely on removed ids no longer appearing in
+/// `search` results.
+pub trait VectorIndex: Send + Sync {
+    /// Builds a fresh index from scratch, discarding any prior state.
+    fn build(items: &[(usize, Vec<f64>)]) -> Self
+    where
+        Self: Sized;
+
+    /// Inserts a single new vector under `id`. `id` must not already be present.
+    fn insert(&mut self, id: usize, vector: Vec<f64>);
+
+    /// Logically removes `id` from the index so it no longer appears in `search`.
+    fn remove(&mut self, id: usize);
+
+    /// Returns up to `k` nearest ids to `query`, sorted by descending cosine similarity.
+    fn search(&self, query: &[f64], k: usize) -> Vec<(usize, f64)>;
+
+    /// Whether the caller should discard this index and call `build` again
+    /// from scratch, e.g. because tombstoned deletes have piled up past a
+    /// useful ratio. Defaults to `false`; only `HnswIndex` ever asks for a
+    /// rebuild, since `BruteForceIndex` drops state immediately on `remove`
+    /// and has nothing to reclaim.
+    fn needs_rebuild(&self) -> bool {
+        false
+    }
+}
+
+/// The original O(n) linear scan, kept as the default backend and as a
+/// correctness baseline for testing `HnswIndex` against.
+#[derive(Debug, Default)]
+pub struct BruteForceIndex {
+    vectors: HashMap<usize, Vec<f64>>,
+}
+
+impl VectorIndex for BruteForceIndex {
+    fn build(items: &[(usize, Vec<f64>)]) -> Self {
+        Self {
+            vectors: items.iter().cloned().collect(),
+        }
+    }
+
+    fn insert(&mut self, id: usize, vector: Vec<f64>) {
+        self.vectors.insert(id, vector);
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.vectors.remove(&id);
+    }
+
+    fn search(&self, query: &[f64], k: usize) -> Vec<(usize, f64)> {
+        let mut scored: Vec<(usize, f64)> = self
+            .vectors
+            .iter()
+            .map(|(&id, vector)| (id, cosine_similarity(query, vector)))
+            .collect();
+        scored.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        scored.truncate(k);
+        scored
+    }
+}
+
+/// A small, dependency-free splitmix64 PRNG used only to draw the random
+/// per-node max layer at insertion time. Good enough for level assignment,
+/// which has no correctness requirement beyond "roughly exponential".
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns a uniform float in `(0, 1)`, never 0 so `ln()` stays finite.
+    fn next_unit(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        // Map to (0, 1), avoiding exactly 0.
+        ((z >> 11) as f64 + 1.0) / ((1u64 << 53) as f64 + 1.0)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HnswNode {
+    vector: Vec<f64>,
+    /// `neighbors[layer]` holds this node's neighbor ids at that layer.
+    neighbors: Vec<Vec<usize>>,
+}
+
+/// Above this tombstone-to-node ratio, [`HnswIndex::needs_rebuild`] reports
+/// `true`: enough deleted nodes have accumulated that searches are wasting
+/// time walking past them, and a fresh `build` is cheaper than continuing to
+/// carry the dead weight.
+const TOMBSTONE_REBUILD_RATIO: f64 = 0.2;
+
+fn default_rng() -> SplitMix64 {
+    SplitMix64::new(0x2545F4914F6CDD1D)
+}
+
+/// A Hierarchical Navigable Small World graph over cosine-similarity vectors.
+///
+/// On insert, the new node draws a random top layer `l = floor(-ln(u) * mL)`
+/// (`mL = 1 / ln(M)`), then for each layer from `l` down to `0` greedily walks
+/// from the current entry point to the nearest neighbors and links the new
+/// node to up to `M` of them (`2*M` at layer 0), chosen by the diversity
+/// heuristic in `prune_neighbors`, pruning neighbor lists back down afterwards. Search greedily descends the upper layers keeping a single
+/// closest node, then runs an `ef_search`-bounded best-first search at layer 0.
+///
+/// Deletes are tombstones: HNSW graphs can't cheaply unlink a node without
+/// risking disconnecting the graph, so `remove` just hides the id from search
+/// results and callers are expected to rebuild periodically once the
+/// tombstone ratio gets high.
+#[derive(Serialize, Deserialize)]
+pub struct HnswIndex {
+    nodes: HashMap<usize, HnswNode>,
+    tombstones: HashSet<usize>,
+    entry_point: Option<usize>,
+    m: usize,
+    ef_construction: usize,
+    ef_search: usize,
+    ml: f64,
+    #[serde(skip, default = "default_rng")]
+    rng: SplitMix64,
+}
+
+#[derive(PartialEq)]
+struct ScoredId {
+    id: usize,
+    similarity: f64,
+}
+
+impl Eq for ScoredId {}
+impl Ord for ScoredId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.similarity
+            .partial_cmp(&other.similarity)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+impl PartialOrd for ScoredId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl HnswIndex {
+    pub fn with_params(m: usize, ef_construction: usize, ef_search: usize) -> Self {
+        let m = m.max(2);
+        Self {
+            nodes: HashMap::new(),
+            tombstones: HashSet::new(),
+            entry_point: None,
+            m,
+            ef_construction: ef_construction.max(m),
+            ef_search: ef_search.max(m),
+            ml: 1.0 / (m as f64).ln(),
+            rng: default_rng(),
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        (-self.rng.next_unit().ln() * self.ml).floor() as usize
+    }
+
+    fn is_live(&self, id: usize) -> bool {
+        self.nodes.contains_key(&id) && !self.tombstones.contains(&id)
+    }
+
+    /// Total nodes in the graph, live or tombstoned. Used to size-check a
+    /// persisted graph against the current corpus before reusing it.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn similarity_between(&self, a: usize, b: usize) -> f64 {
+        match (self.nodes.get(&a), self.nodes.get(&b)) {
+            (Some(na), Some(nb)) => cosine_similarity(&na.vector, &nb.vector),
+            _ => f64::NEG_INFINITY,
+        }
+    }
+
+    /// Greedily walks from `entry` towards the nearest neighbor of `query` at `layer`.
+    fn greedy_search_layer(&self, query: &[f64], entry: usize, layer: usize) -> usize {
+        let mut current = entry;
+        let mut current_sim = self.similarity_to(query, current);
+        loop {
+            let mut improved = false;
+            if let Some(node) = self.nodes.get(&current) {
+                if let Some(neighbors) = node.neighbors.get(layer) {
+                    for &candidate in neighbors {
+                        if !self.is_live(candidate) {
+                            continue;
+                        }
+                        let sim = self.similarity_to(query, candidate);
+                        if sim > current_sim {
+                            current = candidate;
+                            current_sim = sim;
+                            improved = true;
+                        }
+                    }
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first search at `layer` bounded by `ef`, starting from `entry`.
+    fn search_layer(&self, query: &[f64], entry: usize, layer: usize, ef: usize) -> Vec<ScoredId> {
+        let mut visited = HashSet::new();
+        visited.insert(entry);
+
+        let entry_sim = self.similarity_to(query, entry);
+        let mut candidates = BinaryHeap::new();
+        candidates.push(ScoredId {
+            id: entry,
+            similarity: entry_sim,
+        });
+
+        let mut best: Vec<ScoredId> = vec![ScoredId {
+            id: entry,
+            similarity: entry_sim,
+        }];
+
+        while let Some(ScoredId { id, similarity }) = candidates.pop() {
+            let worst_in_best = best
+                .iter()
+                .map(|s| s.similarity)
+                .fold(f64::INFINITY, f64::min);
+            if best.len() >= ef && similarity < worst_in_best {
+                break;
+            }
+
+            if let Some(node) = self.nodes.get(&id) {
+                if let Some(neighbors) = node.neighbors.get(layer) {
+                    for &neighbor in neighbors {
+                        if !self.is_live(neighbor) || visited.contains(&neighbor) {
+                            continue;
+                        }
+                        visited.insert(neighbor);
+                        let sim = self.similarity_to(query, neighbor);
+                        candidates.push(ScoredId {
+                            id: neighbor,
+                            similarity: sim,
+                        });
+                        best.push(ScoredId {
+                            id: neighbor,
+                            similarity: sim,
+                        });
+                    }
+                }
+            }
+
+            best.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(Ordering::Equal));
+            best.truncate(ef);
+        }
+
+        best
+    }
+
+    fn similarity_to(&self, query: &[f64], id: usize) -> f64 {
+        self.nodes
+            .get(&id)
+            .map(|n| cosine_similarity(query, &n.vector))
+            .unwrap_or(f64::NEG_INFINITY)
+    }
+
+    /// Selects up to `limit` of `candidates` as neighbors for `vector`,
+    /// preferring diverse directions over pure nearest-first: a candidate is
+    /// kept only if it's closer to `vector` than to every neighbor already
+    /// selected, which spreads links across different regions of the graph
+    /// instead of clustering them all on one side of it. Candidates rejected
+    /// for being redundant with an already-selected neighbor backfill the
+    /// remaining budget, nearest first, so the node still gets `limit` links
+    /// when the candidate pool is too small to be picky.
+    fn prune_neighbors(&self, vector: &[f64], mut candidates: Vec<usize>, limit: usize) -> Vec<usize> {
+        candidates.sort_by(|&a, &b| {
+            let sim_a = self.similarity_to(vector, a);
+            let sim_b = self.similarity_to(vector, b);
+            sim_b.partial_cmp(&sim_a).unwrap_or(Ordering::Equal)
+        });
+
+        let mut selected: Vec<usize> = Vec::new();
+        let mut rejected: Vec<usize> = Vec::new();
+        for candidate in candidates {
+            if selected.len() >= limit {
+                rejected.push(candidate);
+                continue;
+            }
+            let candidate_sim = self.similarity_to(vector, candidate);
+            let is_diverse = selected
+                .iter()
+                .all(|&kept| candidate_sim > self.similarity_between(candidate, kept));
+            if is_diverse {
+                selected.push(candidate);
+            } else {
+                rejected.push(candidate);
+            }
+        }
+
+        let shortfall = limit - selected.len();
+        if shortfall > 0 {
+            selected.extend(rejected.into_iter().take(shortfall));
+        }
+        selected
+    }
+}
+
+impl VectorIndex for HnswIndex {
+    fn build(items: &[(usize, Vec<f64>)]) -> Self {
+        let mut index = HnswIndex::with_params(16, 200, 64);
+        for (id, vector) in items {
+            index.insert(*id, vector.clone());
+        }
+        index
+    }
+
+    fn insert(&mut self, id: usize, vector: Vec<f64>) {
+        let level = self.random_level();
+
+        let Some(entry_point) = self.entry_point else {
+            self.nodes.insert(
+                id,
+                HnswNode {
+                    vector,
+                    neighbors: vec![Vec::new(); level + 1],
+                },
+            );
+            self.entry_point = Some(id);
+            return;
+        };
+
+        let top_layer = self
+            .nodes
+            .get(&entry_point)
+            .map(|n| n.neighbors.len() - 1)
+            .unwrap_or(0);
+
+        // Descend greedily from the top layer down to `level + 1`, keeping just
+        // the single closest node found so far as the next layer's entry point.
+        let mut nearest = entry_point;
+        for layer in (level + 1..=top_layer).rev() {
+            nearest = self.greedy_search_layer(&vector, nearest, layer);
+        }
+
+        let mut neighbors_per_layer = vec![Vec::new(); level + 1];
+
+        // Insert the node itself *before* wiring up back-links, with its
+        // real vector but empty neighbor lists (filled in below). Standard
+        // HNSW order: `prune_neighbors` on a saturated neighbor compares
+        // `id`'s similarity against its existing links (`similarity_to`,
+        // `similarity_between`), and if `id` isn't in `self.nodes` yet those
+        // both resolve to `f64::NEG_INFINITY`, so the new back-link always
+        // loses the diversity comparison and gets pruned away — leaving the
+        // node with outbound links but no inbound ones, unreachable from the
+        // entry point.
+        self.nodes.insert(
+            id,
+            HnswNode {
+                vector: vector.clone(),
+                neighbors: neighbors_per_layer.clone(),
+            },
+        );
+
+        for layer in (0..=level.min(top_layer)).rev() {
+            let candidates = self.search_layer(&vector, nearest, layer, self.ef_construction);
+            nearest = candidates.first().map(|s| s.id).unwrap_or(nearest);
+
+            let layer_m = if layer == 0 { self.m * 2 } else { self.m };
+            let candidate_ids: Vec<usize> = candidates
+                .into_iter()
+                .map(|s| s.id)
+                .filter(|&c| c != id)
+                .collect();
+            let chosen = self.prune_neighbors(&vector, candidate_ids, layer_m);
+
+            for &neighbor in &chosen {
+                if let Some(node) = self.nodes.get_mut(&neighbor) {
+                    if let Some(back_links) = node.neighbors.get_mut(layer) {
+                        back_links.push(id);
+                        let neighbor_vector = node.vector.clone();
+                        let pruned = self.prune_neighbors(
+                            &neighbor_vector,
+                            std::mem::take(back_links),
+                            layer_m,
+                        );
+                        if let Some(node) = self.nodes.get_mut(&neighbor) {
+                            node.neighbors[layer] = pruned;
+                        }
+                    }
+                }
+            }
+
+            neighbors_per_layer[layer] = chosen;
+        }
+
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.neighbors = neighbors_per_layer;
+        }
+
+        if level > top_layer {
+            self.entry_point = Some(id);
+        }
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.tombstones.insert(id);
+        // If the entry point was tombstoned, fall back to any other live node
+        // so subsequent searches still have somewhere to start from.
+        if self.entry_point == Some(id) {
+            self.entry_point = self
+                .nodes
+                .keys()
+                .find(|&&candidate| candidate != id && !self.tombstones.contains(&candidate))
+                .copied();
+        }
+    }
+
+    fn search(&self, query: &[f64], k: usize) -> Vec<(usize, f64)> {
+        let Some(entry_point) = self.entry_point else {
+            return Vec::new();
+        };
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let top_layer = self
+            .nodes
+            .get(&entry_point)
+            .map(|n| n.neighbors.len() - 1)
+            .unwrap_or(0);
+
+        let mut nearest = entry_point;
+        for layer in (1..=top_layer).rev() {
+            nearest = self.greedy_search_layer(query, nearest, layer);
+        }
+
+        let ef = self.ef_search.max(k);
+        let mut results = self.search_layer(query, nearest, 0, ef);
+        results.retain(|s| self.is_live(s.id));
+        results.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(Ordering::Equal));
+        results.truncate(k);
+        results.into_iter().map(|s| (s.id, s.similarity)).collect()
+    }
+
+    fn needs_rebuild(&self) -> bool {
+        let total = self.nodes.len();
+        total > 0 && (self.tombstones.len() as f64 / total as f64) > TOMBSTONE_REBUILD_RATIO
+    }
+}
@@ -0,0 +1,88 @@
+//! src/qa/ingestion.rs
+//!
+//! Splits long-form text (a pasted message or an uploaded document) into
+//! overlapping, independently embeddable chunks so the bot can match against
+//! passages of a document instead of requiring every fact to be hand-written
+//! as a Q&A pair.
+
+/// Splits `text` into chunks of roughly `chunk_chars` characters with an
+/// `overlap_chars` overlap between consecutive chunks, preferring to break on
+/// paragraph boundaries (blank lines) and falling back to sentence boundaries
+/// (after any of `.`, `。`, `!`, `！`, `?`, `？`, `\n`) when a paragraph itself
+/// is longer than `chunk_chars`.
+pub fn chunk_text(text: &str, chunk_chars: usize, overlap_chars: usize) -> Vec<String> {
+    let chunk_chars = chunk_chars.max(1);
+    let overlap_chars = overlap_chars.min(chunk_chars.saturating_sub(1));
+
+    let paragraphs: Vec<&str> = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    let mut units: Vec<&str> = Vec::new();
+    for paragraph in paragraphs {
+        if paragraph.chars().count() <= chunk_chars {
+            units.push(paragraph);
+        } else {
+            units.extend(split_into_sentences(paragraph));
+        }
+    }
+    if units.is_empty() {
+        return Vec::new();
+    }
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for unit in units {
+        if !current.is_empty() && current.chars().count() + unit.chars().count() > chunk_chars {
+            chunks.push(current.clone());
+            current = take_tail_chars(&current, overlap_chars);
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(unit);
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Breaks a paragraph into sentence-ish units on common terminators.
+fn split_into_sentences(paragraph: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let bytes = paragraph.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let ch = paragraph[i..].chars().next().unwrap();
+        let boundary = matches!(ch, '.' | '。' | '!' | '！' | '?' | '？' | '\n');
+        let ch_len = ch.len_utf8();
+        if boundary {
+            let end = i + ch_len;
+            let sentence = paragraph[start..end].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            start = end;
+        }
+        i += ch_len;
+    }
+    let tail = paragraph[start..].trim();
+    if !tail.is_empty() {
+        sentences.push(tail);
+    }
+    sentences
+}
+
+/// Returns the trailing `max_chars` characters of `s`, used to seed the next
+/// chunk with overlap from the one just closed out.
+fn take_tail_chars(s: &str, max_chars: usize) -> String {
+    let total = s.chars().count();
+    if total <= max_chars {
+        return s.to_string();
+    }
+    s.chars().skip(total - max_chars).collect()
+}
@@ -1,80 +1,64 @@
 //! src/qa/management.rs
 //!
-//! This module provides high-level functions for managing the lifecycle of Q&A items.
-//! It encapsulates the logic for persistence (modifying the JSON file) and state update
-//! (reloading and re-embedding data) to ensure consistency and reduce code
-//! duplication in the bot handlers.
+//! Decoupled pub/sub for QA lifecycle changes. `QAService::add_qa`,
+//! `update_qa`, and `delete_qa` bundle persistence plus re-indexing, but
+//! until now there was no way for anything else to react once those commit.
+//! Inspired by Helix's hook/event system, this gives callers a
+//! [`QaEventBus`] to subscribe to instead of threading bespoke callbacks
+//! through every management function — useful for things like audit
+//! logging, pushing admin notifications, or invalidating an external search
+//! cache.
 
-use crate::{
-    config::Config,
-    gemini::key_manager::GeminiKeyManager,
-    qa::{
-        persistence::{add_qa_item_to_json, delete_qa_item_by_hash, update_qa_item_by_hash},
-        types::{FormattedText, QAItem, QASystem},
-    },
-};
-use anyhow::Result;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 
-/// Adds a new Q&A item, saves it to the JSON file, and triggers a reload of the embeddings.
-pub async fn add_qa(
-    config: &Config,
-    key_manager: &Arc<GeminiKeyManager>,
-    qa_system_mutex: &Mutex<QASystem>,
-    question: &FormattedText,
-    answer: &FormattedText,
-) -> Result<()> {
-    // Step 1: Create the new item and persist it
-    let new_item = QAItem {
-        question: question.clone(),
-        answer: answer.clone(),
-    };
-    add_qa_item_to_json(config, &new_item)?;
-
-    // Step 2: Lock and reload the in-memory embeddings
-    let mut qa_guard = qa_system_mutex.lock().await;
-    qa_guard.load_and_embed_qa(config, key_manager).await?;
+/// A QA lifecycle change, published only after the corresponding
+/// `QAService` mutation has committed (persisted and re-indexed).
+#[derive(Debug, Clone)]
+pub enum QaEvent {
+    /// A new item was added, keyed by its question hash.
+    Added { question_hash: String },
+    /// An item's question and/or answer changed. `old_hash` identifies the
+    /// entry that was replaced; `new_hash` is its hash afterwards, which
+    /// differs from `old_hash` whenever the question text changed.
+    Updated { old_hash: String, new_hash: String },
+    /// An item was removed, keyed by its (former) question hash.
+    Deleted { question_hash: String },
+}
 
-    Ok(())
+/// Broadcast channel over which subscribers receive [`QaEvent`]s. Cheap to
+/// clone (it just clones the underlying `broadcast::Sender`), so `QAService`
+/// can hand out subscriptions without wrapping it in an `Arc` itself.
+#[derive(Clone)]
+pub struct QaEventBus {
+    sender: broadcast::Sender<QaEvent>,
 }
 
-/// Deletes a Q&A item by its question hash, saves the change, and triggers a reload.
-pub async fn delete_qa(
-    config: &Config,
-    key_manager: &Arc<GeminiKeyManager>,
-    qa_system_mutex: &Mutex<QASystem>,
-    question_hash: &str,
-) -> Result<()> {
-    // Step 1: Persist the deletion
-    delete_qa_item_by_hash(config, question_hash)?;
+impl QaEventBus {
+    /// Creates a bus that buffers up to `capacity` events for a subscriber
+    /// that falls behind before it starts missing them.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
 
-    // Step 2: Lock and reload
-    let mut qa_guard = qa_system_mutex.lock().await;
-    qa_guard.load_and_embed_qa(config, key_manager).await?;
+    /// Registers a new subscriber. Events published before this call is made
+    /// are not replayed.
+    pub fn subscribe(&self) -> broadcast::Receiver<QaEvent> {
+        self.sender.subscribe()
+    }
 
-    Ok(())
+    /// Publishes `event` to every current subscriber. Fire-and-forget: if
+    /// nobody is listening, `send` returns an error that we intentionally
+    /// ignore.
+    pub fn publish(&self, event: QaEvent) {
+        let _ = self.sender.send(event);
+    }
 }
 
-/// Updates an existing Q&A item by its old question hash and triggers a reload.
-pub async fn update_qa(
-    config: &Config,
-    key_manager: &Arc<GeminiKeyManager>,
-    qa_system_mutex: &Mutex<QASystem>,
-    old_question_hash: &str,
-    new_question: &FormattedText,
-    new_answer: &FormattedText,
-) -> Result<()> {
-    // Step 1: Create the updated item and persist the change
-    let new_item = QAItem {
-        question: new_question.clone(),
-        answer: new_answer.clone(),
-    };
-    update_qa_item_by_hash(config, old_question_hash, &new_item)?;
-
-    // Step 2: Lock and reload
-    let mut qa_guard = qa_system_mutex.lock().await;
-    qa_guard.load_and_embed_qa(config, key_manager).await?;
-
-    Ok(())
+impl Default for QaEventBus {
+    /// 64 buffered events is generous for the handful of slow subscribers
+    /// (audit log, notifications) this is expected to serve.
+    fn default() -> Self {
+        Self::new(64)
+    }
 }
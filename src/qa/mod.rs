@@ -1,9 +1,15 @@
 mod embedding;
+pub mod gossip;
+pub mod index;
+pub mod ingestion;
+pub mod management;
 pub mod persistence;
 pub mod search;
 pub mod service;
+pub mod store;
 pub mod types;
 mod utils;
+pub mod watcher;
 
 // Re-export the primary service and key types for easy access from other modules.
 pub use service::QAService;
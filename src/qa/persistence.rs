@@ -1,7 +1,9 @@
 use crate::config::Config;
+use crate::qa::index::HnswIndex;
 use crate::qa::types::QAItem;
 use crate::qa::utils;
 use anyhow::{Context, Result};
+use rusqlite::Connection;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -49,7 +51,7 @@ pub fn save_all_qa_items(qa_json_path: &str, items: &[QAItem]) -> Result<()> {
 }
 
 /// Gets the canonical path to the embeddings cache file based on config.
-fn get_cache_path(config: &Config) -> Result<PathBuf> {
+pub(crate) fn get_cache_path(config: &Config) -> Result<PathBuf> {
     let model_name_sanitized = config
         .embedding
         .model
@@ -111,3 +113,236 @@ pub fn add_embedding_to_cache(
     save_embeddings_cache(&cache_path, &cache)?;
     Ok(())
 }
+
+// --- HNSW graph persistence ---
+//
+// Caches the built `HnswIndex` graph to the cache dir so a restart can skip
+// re-indexing the whole corpus. Keyed by embedding model, like the JSON
+// embeddings cache, since a graph built over one model's vectors is useless
+// for another. Saved alongside a `utils::corpus_fingerprint` of the corpus it
+// was built from; `QAService::rebuild_index` treats this as a best-effort
+// fast path, only reusing a loaded graph whose fingerprint matches the
+// current corpus exactly, and otherwise rebuilding and re-saving.
+
+/// On-disk representation of a persisted HNSW graph: the graph itself plus
+/// the fingerprint of the corpus it was built from, so a reload can detect
+/// any add/remove/reorder/question-edit since the graph was saved.
+#[derive(serde::Deserialize)]
+struct PersistedHnswGraph {
+    fingerprint: String,
+    index: HnswIndex,
+}
+
+/// Borrowing counterpart of [`PersistedHnswGraph`] used when writing, so
+/// `save_hnsw_index` doesn't need to clone the graph it was handed.
+#[derive(serde::Serialize)]
+struct PersistedHnswGraphRef<'a> {
+    fingerprint: &'a str,
+    index: &'a HnswIndex,
+}
+
+/// Bumped whenever a change to `HnswIndex::insert`/graph layout can make a
+/// previously-saved file unsafe to reuse as-is, so it's baked into the cache
+/// file name and old files are simply never read rather than loaded and
+/// silently misinterpreted. Currently at 2: v1 graphs were built by an
+/// `insert` that linked a saturated neighbor back to the new node *before*
+/// that node existed in `self.nodes`, which always lost the diversity
+/// comparison and left the node with no inbound edges.
+const HNSW_CACHE_FORMAT_VERSION: u32 = 2;
+
+/// Gets the path to the persisted HNSW graph based on config.
+pub(crate) fn get_hnsw_graph_path(config: &Config) -> Result<PathBuf> {
+    let model_name_sanitized = config
+        .embedding
+        .model
+        .replace(|c: char| !c.is_alphanumeric(), "_");
+    let cache_dir = Path::new(&config.cache.dir);
+    fs::create_dir_all(cache_dir)
+        .with_context(|| format!("Failed to create cache directory: {:?}", cache_dir))?;
+    Ok(cache_dir.join(format!(
+        "hnsw_graph_v{}_{}.json",
+        HNSW_CACHE_FORMAT_VERSION, model_name_sanitized
+    )))
+}
+
+/// Loads the persisted HNSW graph and the fingerprint of the corpus it was
+/// built from, if one was saved by a prior run. Returns `None` if no graph
+/// file exists yet, or it can't be parsed (e.g. it was built by an
+/// incompatible older format), so the caller falls back to building a fresh
+/// one.
+pub fn load_hnsw_index(config: &Config) -> Result<Option<(String, HnswIndex)>> {
+    let path = get_hnsw_graph_path(config)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let file = fs::File::open(&path)
+        .with_context(|| format!("Failed to open HNSW graph at {:?}", path))?;
+    match serde_json::from_reader::<_, PersistedHnswGraph>(std::io::BufReader::new(file)) {
+        Ok(persisted) => Ok(Some((persisted.fingerprint, persisted.index))),
+        Err(e) => {
+            log::warn!(
+                "Failed to parse persisted HNSW graph, will rebuild. Error: {}",
+                e
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Persists the HNSW graph, tagged with `fingerprint` (see
+/// `utils::corpus_fingerprint`), to the cache dir, overwriting any prior
+/// save.
+pub fn save_hnsw_index(config: &Config, fingerprint: &str, index: &HnswIndex) -> Result<()> {
+    let path = get_hnsw_graph_path(config)?;
+    let persisted = PersistedHnswGraphRef { fingerprint, index };
+    let json_string =
+        serde_json::to_string_pretty(&persisted).context("Failed to serialize HNSW graph")?;
+    fs::write(&path, json_string)
+        .with_context(|| format!("Failed to write HNSW graph to {:?}", path))?;
+    log::info!("Saved HNSW graph with {} nodes to {:?}", index.len(), path);
+    Ok(())
+}
+
+// --- SQLite-backed embeddings cache ---
+//
+// An alternative to the JSON cache above: rows of
+// `(question_hash, model_name, dimensions, embedding)`, with the embedding
+// packed as a little-endian `f64` byte blob. Unlike the JSON cache, a single
+// embedding can be upserted without reading or rewriting the whole file, and
+// multiple embedding models can share one database keyed by `model_name`.
+
+/// Gets the path to the SQLite embeddings database based on config.
+pub(crate) fn get_sqlite_path(config: &Config) -> Result<PathBuf> {
+    let cache_dir = Path::new(&config.cache.dir);
+    fs::create_dir_all(cache_dir)
+        .with_context(|| format!("Failed to create cache directory: {:?}", cache_dir))?;
+    Ok(cache_dir.join(&config.storage.sqlite_path))
+}
+
+/// Opens the SQLite embeddings database, creating its schema if needed.
+fn open_sqlite_connection(config: &Config) -> Result<Connection> {
+    let db_path = get_sqlite_path(config)?;
+    let conn = Connection::open(&db_path)
+        .with_context(|| format!("Failed to open SQLite database at {:?}", db_path))?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS embeddings (
+            question_hash TEXT NOT NULL,
+            model_name    TEXT NOT NULL,
+            dimensions    INTEGER NOT NULL,
+            embedding     BLOB NOT NULL,
+            PRIMARY KEY (question_hash, model_name)
+        )",
+        (),
+    )
+    .context("Failed to create embeddings table")?;
+    Ok(conn)
+}
+
+/// Packs a vector of `f64`s into a little-endian byte blob for storage.
+fn pack_embedding(embedding: &[f64]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Reverses [`pack_embedding`].
+fn unpack_embedding(bytes: &[u8]) -> Vec<f64> {
+    bytes
+        .chunks_exact(8)
+        .map(|chunk| f64::from_le_bytes(chunk.try_into().expect("chunks_exact(8) yields 8 bytes")))
+        .collect()
+}
+
+/// Loads every cached embedding for `config.embedding.model` from the SQLite
+/// database. Missing database file is treated as an empty cache.
+pub fn load_embeddings_cache_sqlite(config: &Config) -> Result<HashMap<String, Vec<f64>>> {
+    let conn = open_sqlite_connection(config)?;
+    let mut stmt = conn
+        .prepare("SELECT question_hash, embedding FROM embeddings WHERE model_name = ?1")
+        .context("Failed to prepare embeddings select statement")?;
+    let rows = stmt
+        .query_map([&config.embedding.model], |row| {
+            let question_hash: String = row.get(0)?;
+            let blob: Vec<u8> = row.get(1)?;
+            Ok((question_hash, unpack_embedding(&blob)))
+        })
+        .context("Failed to query embeddings table")?;
+
+    let mut cache = HashMap::new();
+    for row in rows {
+        let (question_hash, embedding) = row.context("Failed to read embeddings row")?;
+        cache.insert(question_hash, embedding);
+    }
+    Ok(cache)
+}
+
+/// Upserts a single question's embedding into the SQLite database, without
+/// touching any other row.
+pub fn add_embedding_to_cache_sqlite(
+    config: &Config,
+    question_text: &str,
+    embedding: &[f64],
+) -> Result<()> {
+    let conn = open_sqlite_connection(config)?;
+    let question_hash = utils::get_question_hash(question_text);
+    conn.execute(
+        "INSERT INTO embeddings (question_hash, model_name, dimensions, embedding)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(question_hash, model_name) DO UPDATE SET
+            dimensions = excluded.dimensions,
+            embedding = excluded.embedding",
+        rusqlite::params![
+            question_hash,
+            config.embedding.model,
+            embedding.len() as i64,
+            pack_embedding(embedding),
+        ],
+    )
+    .context("Failed to upsert embedding into SQLite cache")?;
+    Ok(())
+}
+
+/// Upserts every entry of `cache` into the SQLite database in one transaction,
+/// for bulk saves (e.g. migrating an in-memory cache built some other way).
+pub fn save_embeddings_cache_sqlite(
+    config: &Config,
+    cache: &HashMap<String, Vec<f64>>,
+) -> Result<()> {
+    let mut conn = open_sqlite_connection(config)?;
+    let tx = conn
+        .transaction()
+        .context("Failed to start SQLite transaction")?;
+    for (question_hash, embedding) in cache {
+        tx.execute(
+            "INSERT INTO embeddings (question_hash, model_name, dimensions, embedding)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(question_hash, model_name) DO UPDATE SET
+                dimensions = excluded.dimensions,
+                embedding = excluded.embedding",
+            rusqlite::params![
+                question_hash,
+                config.embedding.model,
+                embedding.len() as i64,
+                pack_embedding(embedding),
+            ],
+        )
+        .context("Failed to upsert embedding into SQLite cache")?;
+    }
+    tx.commit().context("Failed to commit SQLite transaction")?;
+    log::info!(
+        "Saved {} embeddings to SQLite cache: {:?}",
+        cache.len(),
+        get_sqlite_path(config)?
+    );
+    Ok(())
+}
+
+/// One-time import of the legacy JSON embeddings cache into the SQLite
+/// database, for operators migrating `storage.backend` from `LocalFs` to
+/// `Sqlite`. Returns the number of entries imported.
+pub fn import_json_cache_into_sqlite(config: &Config) -> Result<usize> {
+    let (_, json_cache) = load_embeddings_cache(config)?;
+    let count = json_cache.len();
+    save_embeddings_cache_sqlite(config, &json_cache)?;
+    log::info!("Imported {} embeddings from the JSON cache into SQLite.", count);
+    Ok(count)
+}
@@ -1,8 +1,9 @@
 use crate::qa::types::QAItem;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
 /// 计算两个 f64 切片的余弦相似度
-fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
+pub(crate) fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
     let dot_product: f64 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
     let norm_a: f64 = a.iter().map(|x| x * x).sum::<f64>().sqrt();
     let norm_b: f64 = b.iter().map(|x| x * x).sum::<f64>().sqrt();
@@ -26,6 +27,28 @@ pub fn find_best_match(
         .max_by(|(_, sim_a), (_, sim_b)| sim_a.partial_cmp(sim_b).unwrap_or(Ordering::Equal))
 }
 
+/// Finds the K highest-similarity matches for a query embedding.
+/// Returns up to `k` `(index, similarity)` pairs sorted by descending similarity.
+pub fn find_top_k(
+    query_embedding: &[f64],
+    question_embeddings: &[Vec<f64>],
+    k: usize,
+) -> Vec<(usize, f64)> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(usize, f64)> = question_embeddings
+        .iter()
+        .enumerate()
+        .map(|(index, q_embedding)| (index, cosine_similarity(query_embedding, q_embedding)))
+        .collect();
+
+    scored.sort_by(|(_, sim_a), (_, sim_b)| sim_b.partial_cmp(sim_a).unwrap_or(Ordering::Equal));
+    scored.truncate(k);
+    scored
+}
+
 /// Searches for QA items where the question text contains the given keywords.
 /// The search is case-insensitive and returns up to 10 matches.
 pub fn search_by_keyword(qa_data: &[QAItem], keywords: &str) -> Vec<QAItem> {
@@ -40,3 +63,43 @@ pub fn search_by_keyword(qa_data: &[QAItem], keywords: &str) -> Vec<QAItem> {
         .cloned()
         .collect()
 }
+
+/// Like [`search_by_keyword`], but returns positions into `qa_data` (in corpus
+/// order; matches are not ranked by match quality) instead of cloned items, so
+/// callers can fuse the ranking with other rankings over the same corpus (see
+/// [`reciprocal_rank_fusion`]).
+pub fn search_by_keyword_indices(qa_data: &[QAItem], keywords: &str, limit: usize) -> Vec<usize> {
+    if keywords.is_empty() {
+        return Vec::new();
+    }
+    let lower_keywords = keywords.to_lowercase();
+    qa_data
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.question.text.to_lowercase().contains(&lower_keywords))
+        .take(limit)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Fuses multiple rankings of the same corpus (each a list of positions,
+/// best match first) with weighted Reciprocal Rank Fusion: for every
+/// position, sums `weight * 1 / (k + rank)` (rank is 1-based) over the
+/// rankings it appears in, with rankings it's absent from contributing
+/// nothing. `weights` must be the same length as `rankings`, pairing each
+/// ranking with how much it should count toward the fused score — e.g. a
+/// `semantic_ratio` config knob lets operators bias the fusion toward
+/// vector or keyword matches. Returns `(position, fused score)` pairs sorted
+/// by descending score.
+pub fn reciprocal_rank_fusion(rankings: &[&[usize]], weights: &[f64], k: f64) -> Vec<(usize, f64)> {
+    let mut scores: HashMap<usize, f64> = HashMap::new();
+    for (ranking, weight) in rankings.iter().zip(weights.iter()) {
+        for (rank, &position) in ranking.iter().enumerate() {
+            *scores.entry(position).or_insert(0.0) += weight / (k + (rank + 1) as f64);
+        }
+    }
+
+    let mut fused: Vec<(usize, f64)> = scores.into_iter().collect();
+    fused.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    fused
+}
@@ -7,36 +7,240 @@
 //! dependency management. It also contains the optimized CRUD operations.
 
 use super::{
-    embedding, persistence, search,
+    embedding,
+    gossip::{ChangeEvent, GossipHandle, GossipOp},
+    index::{BruteForceIndex, HnswIndex, VectorIndex},
+    ingestion,
+    management::{QaEvent, QaEventBus},
+    persistence, search,
+    store::{self, Store},
     types::{FormattedText, QAItem, QASystem},
     utils,
 };
-use crate::{config::Config, gemini::key_manager::GeminiKeyManager};
+use crate::{
+    config::{Config, IndexBackend},
+    gemini::{generation, key_manager::GeminiKeyManager},
+};
 use anyhow::{Result, anyhow};
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tokio::time::Duration;
 
+/// A synthesized, retrieval-augmented answer along with the indices (into the
+/// QA corpus as returned by [`QAService::get_all_qa_items`]) of the entries it
+/// was grounded on.
+#[derive(Debug, Clone)]
+pub struct GenerativeAnswer {
+    pub text: FormattedText,
+    pub source_indices: Vec<usize>,
+}
+
+/// Outcome of [`QAService::reload_qa_data`]: how many entries from the
+/// reloaded corpus were new, changed, removed, or identical to what was
+/// already loaded. Used by the config/QA file watcher for its log line and
+/// by the `/reload` command to tell an operator what actually happened.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QaReloadSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+fn build_index(backend: &IndexBackend, config: &Config) -> Box<dyn VectorIndex> {
+    match backend {
+        IndexBackend::BruteForce => Box::new(BruteForceIndex::default()),
+        IndexBackend::Hnsw => Box::new(HnswIndex::with_params(
+            config.index.m,
+            config.index.ef_construction,
+            config.index.ef_search,
+        )),
+    }
+}
+
 pub struct QAService {
     system: QASystem,
     pub config: Arc<Config>,
     key_manager: Arc<GeminiKeyManager>,
+    /// Nearest-neighbor index over `system.question_embeddings`, addressed by a
+    /// stable node id rather than vector position so that deletes (which
+    /// compact `system.qa_data`) don't invalidate other entries' ids.
+    index: Box<dyn VectorIndex>,
+    node_by_hash: HashMap<String, usize>,
+    hash_by_node: HashMap<usize, String>,
+    next_node_id: usize,
+    /// Backend holding the QA items and embeddings cache, selected by
+    /// `config.storage.backend`, so the same corpus can be shared across
+    /// bot instances instead of each keeping its own local files.
+    store: Box<dyn Store>,
+    /// Set once [`gossip::start`] has bound a socket; `None` means gossip is
+    /// disabled and CRUD methods behave exactly as before.
+    gossip: Option<Arc<GossipHandle>>,
+    /// Last `(counter, node_id)` applied per question hash, used to resolve
+    /// conflicting concurrent edits with last-writer-wins. Only populated
+    /// when gossip is enabled.
+    last_writer: HashMap<String, (u64, u64)>,
+    /// Publishes a [`QaEvent`] after each `add_qa`/`update_qa`/`delete_qa`
+    /// commits, so other parts of the system (audit logging, admin
+    /// notifications, cache invalidation) can react without being threaded
+    /// through every management function.
+    event_bus: QaEventBus,
 }
 
 impl QAService {
     /// Creates a new, empty QAService.
     pub fn new(config: Arc<Config>, key_manager: Arc<GeminiKeyManager>) -> Self {
+        let index = build_index(&config.index.backend, &config);
+        let store = store::build_store(&config);
         Self {
             system: QASystem::new(),
             config,
             key_manager,
+            index,
+            node_by_hash: HashMap::new(),
+            hash_by_node: HashMap::new(),
+            next_node_id: 0,
+            store,
+            gossip: None,
+            last_writer: HashMap::new(),
+            event_bus: QaEventBus::default(),
         }
     }
 
+    /// Subscribes to QA lifecycle events published by `add_qa`/`update_qa`/
+    /// `delete_qa`. Events published before this call is made are not
+    /// replayed.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<QaEvent> {
+        self.event_bus.subscribe()
+    }
+
+    /// Returns `(healthy, cooling_down, quarantined)` API key counts from
+    /// the underlying [`GeminiKeyManager`], for `/stats` reporting.
+    pub fn key_health(&self) -> (usize, usize, usize) {
+        self.key_manager.key_health()
+    }
+
+    /// Wires up the gossip handle returned by [`gossip::start`] so future
+    /// `add_qa`/`update_qa`/`delete_qa` calls broadcast to peers. A no-op
+    /// setter is fine to skip entirely when gossip is disabled.
+    pub fn set_gossip(&mut self, gossip: Option<Arc<GossipHandle>>) {
+        self.gossip = gossip;
+    }
+
+    /// Swaps the live configuration for `new_config`, picking up changes
+    /// like `similarity.threshold` or `telegram.allowed_group_ids` without a
+    /// restart. Handlers re-read `qa_service.lock().await.config.clone()` on
+    /// every request rather than holding onto a stale snapshot, so this
+    /// takes effect immediately. Used by the config/QA file watcher and the
+    /// `/reload` command.
+    pub fn reload_config(&mut self, new_config: Config) {
+        self.config = Arc::new(new_config);
+    }
+
+    /// Re-reads the QA corpus from disk and applies only what changed since
+    /// the last load, diffed by question hash: new questions are embedded
+    /// and inserted, removed ones are tombstoned, and questions whose answer
+    /// or source changed are updated in place, reusing their existing
+    /// embedding from the cache since the question itself (and therefore
+    /// its hash) didn't change. Cheaper than [`Self::load_and_embed_all`]
+    /// when only a handful of entries actually changed. Used by the
+    /// config/QA file watcher and the `/reload` command.
+    pub async fn reload_qa_data(&mut self) -> Result<QaReloadSummary> {
+        let new_items = self.store.load_qa_items().await?;
+        let mut embeddings_cache = self.store.load_embeddings_cache().await?;
+        let mut cache_was_updated = false;
+
+        let old_by_hash: HashMap<String, QAItem> = self
+            .system
+            .qa_data
+            .iter()
+            .map(|item| (utils::get_question_hash(&item.question.text), item.clone()))
+            .collect();
+        let new_by_hash: HashMap<String, &QAItem> = new_items
+            .iter()
+            .map(|item| (utils::get_question_hash(&item.question.text), item))
+            .collect();
+
+        let mut summary = QaReloadSummary::default();
+
+        let to_remove: Vec<String> = old_by_hash
+            .keys()
+            .filter(|hash| !new_by_hash.contains_key(hash.as_str()))
+            .cloned()
+            .collect();
+        for hash in to_remove {
+            self.remove_local(&hash);
+            self.broadcast_delete(hash.clone()).await;
+            self.event_bus.publish(QaEvent::Deleted {
+                question_hash: hash,
+            });
+            summary.removed += 1;
+        }
+
+        for (hash, new_item) in &new_by_hash {
+            if let Some(old_item) = old_by_hash.get(hash) {
+                if old_item.answer == new_item.answer && old_item.source == new_item.source {
+                    summary.unchanged += 1;
+                    continue;
+                }
+            }
+            let is_new = !old_by_hash.contains_key(hash);
+
+            let embedding = if let Some(cached) = embeddings_cache.get(hash) {
+                cached.clone()
+            } else {
+                let generated =
+                    embedding::embed_one(&self.config, &self.key_manager, &new_item.question)
+                        .await?;
+                embeddings_cache.insert(hash.clone(), generated.clone());
+                cache_was_updated = true;
+                generated
+            };
+
+            self.upsert_local(
+                hash,
+                new_item.question.clone(),
+                new_item.answer.clone(),
+                new_item.source.clone(),
+                embedding.clone(),
+            );
+            self.broadcast_upsert(hash.clone(), new_item.clone(), embedding)
+                .await;
+
+            if is_new {
+                self.event_bus.publish(QaEvent::Added {
+                    question_hash: hash.clone(),
+                });
+                summary.added += 1;
+            } else {
+                self.event_bus.publish(QaEvent::Updated {
+                    old_hash: hash.clone(),
+                    new_hash: hash.clone(),
+                });
+                summary.updated += 1;
+            }
+        }
+
+        if cache_was_updated {
+            self.store.save_embeddings_cache(&embeddings_cache).await?;
+        }
+        // Only rewrite the QA JSON file if something actually changed — the
+        // watcher that calls this method detects further reloads by mtime,
+        // so writing unconditionally would bump the mtime on every no-op
+        // poll and reload/rewrite forever.
+        if summary.added > 0 || summary.updated > 0 || summary.removed > 0 {
+            self.store.save_all_qa_items(&self.system.qa_data).await?;
+        }
+
+        Ok(summary)
+    }
+
     /// Loads QA data from persistence and generates embeddings for any uncached items.
     /// This is the main initialization method.
     pub async fn load_and_embed_all(&mut self) -> Result<()> {
-        self.system.qa_data = persistence::load_qa_items(&self.config.qa.qa_json_path)?;
-        let (cache_path, mut embeddings_cache) = persistence::load_embeddings_cache(&self.config)?;
+        self.system.qa_data = self.store.load_qa_items().await?;
+        let mut embeddings_cache = self.store.load_embeddings_cache().await?;
 
         let num_keys = self.config.embedding.api_keys.len();
         if num_keys == 0 {
@@ -77,13 +281,112 @@ impl QAService {
         }
 
         if cache_was_updated {
-            persistence::save_embeddings_cache(&cache_path, &embeddings_cache)?;
+            self.store.save_embeddings_cache(&embeddings_cache).await?;
         }
 
         self.system.question_embeddings = final_embeddings;
+        self.rebuild_index();
         Ok(())
     }
 
+    /// Rebuilds the nearest-neighbor index from scratch over the current
+    /// `system.qa_data`/`question_embeddings`, reassigning node ids.
+    fn rebuild_index(&mut self) {
+        self.node_by_hash.clear();
+        self.hash_by_node.clear();
+        self.next_node_id = 0;
+
+        let items: Vec<(usize, Vec<f64>)> = self
+            .system
+            .qa_data
+            .iter()
+            .zip(self.system.question_embeddings.iter())
+            .map(|(item, embedding)| {
+                let node_id = self.next_node_id;
+                self.next_node_id += 1;
+                let hash = utils::get_question_hash(&item.question.text);
+                self.node_by_hash.insert(hash.clone(), node_id);
+                self.hash_by_node.insert(node_id, hash);
+                (node_id, embedding.clone())
+            })
+            .collect();
+
+        // Below `hnsw_min_items`, an exact linear scan is already fast and
+        // avoids both the graph-construction overhead and the approximation
+        // error HNSW brings, so fall back to brute force regardless of the
+        // configured backend.
+        let use_hnsw = self.config.index.backend == IndexBackend::Hnsw
+            && items.len() >= self.config.index.hnsw_min_items;
+
+        // A node count match isn't enough to prove the persisted graph still
+        // matches this corpus: an in-place edit (e.g. `update_qa` or an
+        // incremental `reload_qa_data`) keeps the item count but changes
+        // which question hash sits at which node id, and those don't go
+        // through this function so the persisted graph never gets refreshed
+        // for them. The fingerprint captures the full ordered sequence of
+        // question hashes, so any such edit fails the comparison below and
+        // falls through to a fresh build instead of reusing stale geometry.
+        let fingerprint = utils::corpus_fingerprint(&self.system.qa_data);
+
+        self.index = if use_hnsw {
+            match persistence::load_hnsw_index(&self.config) {
+                Ok(Some((persisted_fingerprint, index)))
+                    if persisted_fingerprint == fingerprint =>
+                {
+                    log::info!("Loaded persisted HNSW graph from cache, skipping re-index.");
+                    Box::new(index)
+                }
+                _ => {
+                    let mut index = HnswIndex::with_params(
+                        self.config.index.m,
+                        self.config.index.ef_construction,
+                        self.config.index.ef_search,
+                    );
+                    for (id, vector) in items {
+                        index.insert(id, vector);
+                    }
+                    if let Err(e) = persistence::save_hnsw_index(&self.config, &fingerprint, &index)
+                    {
+                        log::warn!("Failed to persist HNSW graph: {}", e);
+                    }
+                    Box::new(index)
+                }
+            }
+        } else {
+            Box::new(BruteForceIndex::build(&items))
+        };
+    }
+
+    /// Rebuilds the index in place if too many tombstoned deletes have
+    /// accumulated (see [`VectorIndex::needs_rebuild`]), so lookups don't
+    /// slowly degrade as deletes pile up between full reloads.
+    fn maybe_rebuild_index(&mut self) {
+        if self.index.needs_rebuild() {
+            log::info!("Tombstone ratio exceeded threshold; rebuilding nearest-neighbor index.");
+            self.rebuild_index();
+        }
+    }
+
+    /// Looks up the current position of `node_id` within `system.qa_data`.
+    fn position_of_node(&self, node_id: usize) -> Option<usize> {
+        let hash = self.hash_by_node.get(&node_id)?;
+        self.system
+            .qa_data
+            .iter()
+            .position(|item| &utils::get_question_hash(&item.question.text) == hash)
+    }
+
+    /// Finds the top `k` matches for `query_embedding`, resolved to positions in `qa_data`.
+    fn search_index(&self, query_embedding: &[f64], k: usize) -> Vec<(usize, f64)> {
+        self.index
+            .search(query_embedding, k)
+            .into_iter()
+            .filter_map(|(node_id, similarity)| {
+                self.position_of_node(node_id).map(|pos| (pos, similarity))
+            })
+            .collect()
+    }
+
     /// Finds the best matching QA item for a given text query.
     pub async fn find_matching_qa(&self, text: &str) -> Result<Option<QAItem>> {
         if self.system.question_embeddings.is_empty() {
@@ -93,8 +396,11 @@ impl QAService {
         let query_embedding =
             embedding::generate_embedding_with_retry(&self.config, &self.key_manager, text).await?;
 
-        if let Some((index, similarity)) =
-            search::find_best_match(&query_embedding, &self.system.question_embeddings)
+        if self.config.hybrid_search.enabled {
+            return Ok(self.find_matching_qa_hybrid(&query_embedding, text));
+        }
+
+        if let Some((index, similarity)) = self.search_index(&query_embedding, 1).into_iter().next()
         {
             let threshold = self.config.similarity.threshold;
             if similarity >= threshold as f64 {
@@ -123,32 +429,216 @@ impl QAService {
         }
     }
 
+    /// Hybrid retrieval path for [`Self::find_matching_qa`]: fuses the
+    /// cosine-similarity ranking of `query_embedding` against
+    /// `question_embeddings` with the keyword-match ranking of `text` via
+    /// Reciprocal Rank Fusion, weighted by `config.hybrid_search.semantic_ratio`
+    /// so operators can bias the fusion toward vector or keyword matches, and
+    /// accepts the top fused candidate only if both the fused score and its
+    /// underlying vector similarity clear their configured thresholds. Falls
+    /// back to `None` otherwise, exactly like the non-hybrid path does when
+    /// nothing clears the threshold.
+    fn find_matching_qa_hybrid(&self, query_embedding: &[f64], text: &str) -> Option<QAItem> {
+        let hybrid = &self.config.hybrid_search;
+
+        let vector_ranked = self.search_index(query_embedding, hybrid.candidates);
+        let vector_positions: Vec<usize> = vector_ranked.iter().map(|(pos, _)| *pos).collect();
+        let keyword_positions =
+            search::search_by_keyword_indices(&self.system.qa_data, text, hybrid.candidates);
+
+        let fused = search::reciprocal_rank_fusion(
+            &[&vector_positions, &keyword_positions],
+            &[hybrid.semantic_ratio, 1.0 - hybrid.semantic_ratio],
+            hybrid.rrf_k,
+        );
+        let (top_position, fused_score) = fused.into_iter().next()?;
+
+        let vector_similarity = vector_ranked
+            .iter()
+            .find(|(pos, _)| *pos == top_position)
+            .map(|(_, similarity)| *similarity)
+            .unwrap_or(0.0);
+
+        if fused_score >= hybrid.fused_threshold
+            && vector_similarity >= self.config.similarity.threshold as f64
+        {
+            log::info!(
+                "Hybrid match found for query '{}': Q#{} ('{}') fused_score={:.4} vector_similarity={:.4}",
+                text,
+                top_position,
+                self.system.qa_data[top_position].question.text,
+                fused_score,
+                vector_similarity
+            );
+            Some(self.system.qa_data[top_position].clone())
+        } else {
+            log::info!(
+                "Hybrid retrieval found no candidate above thresholds for query: '{}'",
+                text
+            );
+            None
+        }
+    }
+
+    /// Retrieval-augmented alternative to [`Self::find_matching_qa`].
+    ///
+    /// Retrieves the top `config.generation.top_k` QA items that clear
+    /// `config.similarity.threshold`, feeds them to Gemini as grounding context, and
+    /// asks it to synthesize an answer citing which entries it used. If no retrieved
+    /// item clears the threshold, returns `None` just like the non-generative path so
+    /// the bot stays silent rather than hallucinating from nothing.
+    pub async fn find_matching_qa_generative(&self, text: &str) -> Result<Option<GenerativeAnswer>> {
+        if self.system.question_embeddings.is_empty() {
+            return Ok(None);
+        }
+
+        let query_embedding =
+            embedding::generate_embedding_with_retry(&self.config, &self.key_manager, text).await?;
+
+        let threshold = self.config.similarity.threshold as f64;
+        let top_k = self.search_index(&query_embedding, self.config.generation.top_k);
+        let relevant: Vec<(usize, f64)> = top_k
+            .into_iter()
+            .filter(|(_, similarity)| *similarity >= threshold)
+            .collect();
+
+        if relevant.is_empty() {
+            log::info!("No retrieved items cleared the threshold for query: '{}'", text);
+            return Ok(None);
+        }
+
+        let context = relevant
+            .iter()
+            .enumerate()
+            .map(|(position, (index, similarity))| {
+                let item = &self.system.qa_data[*index];
+                format!(
+                    "[{}] Q: {}\nA: {} (similarity: {:.4})",
+                    position + 1,
+                    item.question.text,
+                    item.answer.text,
+                    similarity
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let prompt = format!(
+            "Answer the user's question using only the facts below; cite the entry numbers you used; \
+             if the facts don't cover it, say so.\n\n{}\n\nQuestion: {}",
+            context, text
+        );
+
+        let answer_text =
+            generation::generate_answer_with_retry(&self.config, &self.key_manager, &prompt).await?;
+
+        Ok(Some(GenerativeAnswer {
+            text: FormattedText {
+                text: answer_text,
+                entities: Vec::new(),
+            },
+            source_indices: relevant.into_iter().map(|(index, _)| index).collect(),
+        }))
+    }
+
     /// Adds a new Q&A item, saves it, and updates the in-memory state and embeddings efficiently.
     pub async fn add_qa(&mut self, question: &FormattedText, answer: &FormattedText) -> Result<()> {
         let new_item = QAItem {
             question: question.clone(),
             answer: answer.clone(),
+            source: None,
         };
 
-        // 1. Generate embedding for the new item
-        let new_embedding = embedding::generate_embedding_with_retry(
-            &self.config,
-            &self.key_manager,
-            &new_item.question.text,
-        )
-        .await?;
+        // 1. Generate embedding for just the new item, not the whole corpus.
+        let new_embedding =
+            embedding::embed_one(&self.config, &self.key_manager, &new_item.question).await?;
 
         // 2. Update in-memory state first
+        let node_id = self.next_node_id;
+        self.next_node_id += 1;
+        let question_hash = utils::get_question_hash(&new_item.question.text);
+        self.node_by_hash.insert(question_hash.clone(), node_id);
+        self.hash_by_node.insert(node_id, question_hash);
+        self.index.insert(node_id, new_embedding.clone());
+
         self.system.qa_data.push(new_item.clone());
         self.system.question_embeddings.push(new_embedding.clone());
 
         // 3. Persist the new state to JSON and cache
-        persistence::save_all_qa_items(&self.config.qa.qa_json_path, &self.system.qa_data)?;
-        persistence::add_embedding_to_cache(&self.config, &new_item.question.text, new_embedding)?;
+        self.store.save_all_qa_items(&self.system.qa_data).await?;
+        self.store
+            .add_embedding_to_cache(&new_item.question.text, new_embedding.clone())
+            .await?;
+
+        // 4. Tell other replicas about the new item.
+        let question_hash = utils::get_question_hash(&new_item.question.text);
+        self.broadcast_upsert(question_hash.clone(), new_item, new_embedding)
+            .await;
+
+        // 5. Notify local subscribers that the commit above succeeded.
+        self.event_bus.publish(QaEvent::Added { question_hash });
 
         Ok(())
     }
 
+    /// Splits `text` into overlapping chunks and stores each as a retrievable
+    /// passage attributed to `source_name`, reusing the embeddings cache so
+    /// re-ingesting an unchanged document (same chunk text, same hash) is a
+    /// cache hit rather than a fresh round-trip to Gemini. Returns the number
+    /// of chunks that were newly embedded.
+    pub async fn ingest_document(&mut self, source_name: &str, text: &str) -> Result<usize> {
+        let chunks = ingestion::chunk_text(
+            text,
+            self.config.qa.ingest_chunk_chars,
+            self.config.qa.ingest_chunk_overlap_chars,
+        );
+
+        let mut embeddings_cache = self.store.load_embeddings_cache().await?;
+        let mut newly_embedded = 0;
+
+        for chunk in chunks {
+            let chunk_text = FormattedText {
+                text: chunk.clone(),
+                entities: Vec::new(),
+            };
+            let chunk_hash = utils::get_question_hash(&chunk);
+
+            let embedding = if let Some(cached) = embeddings_cache.get(&chunk_hash) {
+                cached.clone()
+            } else {
+                let generated = embedding::generate_embedding_with_retry(
+                    &self.config,
+                    &self.key_manager,
+                    &chunk,
+                )
+                .await?;
+                embeddings_cache.insert(chunk_hash.clone(), generated.clone());
+                newly_embedded += 1;
+                generated
+            };
+
+            let node_id = self.next_node_id;
+            self.next_node_id += 1;
+            self.node_by_hash.insert(chunk_hash.clone(), node_id);
+            self.hash_by_node.insert(node_id, chunk_hash);
+            self.index.insert(node_id, embedding.clone());
+
+            self.system.qa_data.push(QAItem {
+                question: chunk_text.clone(),
+                answer: chunk_text,
+                source: Some(source_name.to_string()),
+            });
+            self.system.question_embeddings.push(embedding);
+        }
+
+        if newly_embedded > 0 {
+            self.store.save_embeddings_cache(&embeddings_cache).await?;
+        }
+        self.store.save_all_qa_items(&self.system.qa_data).await?;
+
+        Ok(newly_embedded)
+    }
+
     /// Deletes a Q&A item by its question hash efficiently.
     pub async fn delete_qa(&mut self, question_hash: &str) -> Result<()> {
         if let Some(index) = self
@@ -161,9 +651,25 @@ impl QAService {
             self.system.qa_data.remove(index);
             self.system.question_embeddings.remove(index);
 
-            // 2. Persist the new state to JSON
-            persistence::save_all_qa_items(&self.config.qa.qa_json_path, &self.system.qa_data)?;
+            // 2. Tombstone the vector in the index (HNSW graphs can't cheaply
+            // unlink a node, so this just hides it from future searches).
+            if let Some(node_id) = self.node_by_hash.remove(question_hash) {
+                self.hash_by_node.remove(&node_id);
+                self.index.remove(node_id);
+            }
+            self.maybe_rebuild_index();
+
+            // 3. Persist the new state to JSON
+            self.store.save_all_qa_items(&self.system.qa_data).await?;
             // Note: We don't remove from the embedding cache, as it might be useful again.
+
+            // 4. Tell other replicas the item is gone.
+            self.broadcast_delete(question_hash.to_string()).await;
+
+            // 5. Notify local subscribers that the commit above succeeded.
+            self.event_bus.publish(QaEvent::Deleted {
+                question_hash: question_hash.to_string(),
+            });
         }
         Ok(())
     }
@@ -184,31 +690,184 @@ impl QAService {
             let new_item = QAItem {
                 question: new_question.clone(),
                 answer: new_answer.clone(),
+                source: None,
             };
 
-            // 1. Generate new embedding for the potentially updated question
-            let new_embedding = embedding::generate_embedding_with_retry(
-                &self.config,
-                &self.key_manager,
-                &new_item.question.text,
-            )
-            .await?;
+            // 1. Generate a new embedding for just the changed question,
+            // not the whole corpus.
+            let new_embedding =
+                embedding::embed_one(&self.config, &self.key_manager, &new_item.question).await?;
+
+            // 2. Update in-memory state. The question (and therefore its hash)
+            // may have changed, so the old node is tombstoned and a fresh one
+            // inserted rather than updated in place.
+            if let Some(old_node_id) = self.node_by_hash.remove(old_question_hash) {
+                self.hash_by_node.remove(&old_node_id);
+                self.index.remove(old_node_id);
+            }
+            let new_node_id = self.next_node_id;
+            self.next_node_id += 1;
+            let new_hash = utils::get_question_hash(&new_item.question.text);
+            self.node_by_hash.insert(new_hash.clone(), new_node_id);
+            self.hash_by_node.insert(new_node_id, new_hash.clone());
+            self.index.insert(new_node_id, new_embedding.clone());
 
-            // 2. Update in-memory state
             self.system.qa_data[index] = new_item.clone();
             self.system.question_embeddings[index] = new_embedding.clone();
+            self.maybe_rebuild_index();
 
             // 3. Persist the new state
-            persistence::save_all_qa_items(&self.config.qa.qa_json_path, &self.system.qa_data)?;
-            persistence::add_embedding_to_cache(
-                &self.config,
-                &new_item.question.text,
-                new_embedding,
-            )?;
+            self.store.save_all_qa_items(&self.system.qa_data).await?;
+            self.store
+                .add_embedding_to_cache(&new_item.question.text, new_embedding.clone())
+                .await?;
+
+            // 4. Tell other replicas about the update. The event is keyed by
+            // the *old* hash so receivers can find the entry to replace.
+            self.broadcast_upsert(old_question_hash.to_string(), new_item, new_embedding)
+                .await;
+
+            // 5. Notify local subscribers that the commit above succeeded.
+            self.event_bus.publish(QaEvent::Updated {
+                old_hash: old_question_hash.to_string(),
+                new_hash,
+            });
         }
         Ok(())
     }
 
+    /// Broadcasts an upsert to peers (no-op if gossip is disabled) and
+    /// records the timestamp it was sent with for later conflict resolution.
+    async fn broadcast_upsert(&mut self, question_hash: String, item: QAItem, embedding: Vec<f64>) {
+        let Some(gossip) = self.gossip.clone() else {
+            return;
+        };
+        let timestamp = gossip
+            .broadcast(
+                question_hash.clone(),
+                GossipOp::Upsert {
+                    question: item.question,
+                    answer: item.answer,
+                    source: item.source,
+                    embedding,
+                },
+            )
+            .await;
+        self.last_writer.insert(question_hash, timestamp);
+    }
+
+    /// Broadcasts a delete to peers (no-op if gossip is disabled).
+    async fn broadcast_delete(&mut self, question_hash: String) {
+        let Some(gossip) = self.gossip.clone() else {
+            return;
+        };
+        let timestamp = gossip.broadcast(question_hash.clone(), GossipOp::Delete).await;
+        self.last_writer.insert(question_hash, timestamp);
+    }
+
+    /// Applies a mutation received from a peer over gossip. Ignored if a
+    /// newer write for the same question hash has already been applied
+    /// (last-writer-wins on the `(counter, node_id)` timestamp).
+    pub async fn apply_remote_event(&mut self, event: ChangeEvent) -> Result<()> {
+        let timestamp = (event.counter, event.node_id);
+        if let Some(existing) = self.last_writer.get(&event.question_hash) {
+            if *existing >= timestamp {
+                log::info!(
+                    "Ignoring stale gossip event for question {}",
+                    event.question_hash
+                );
+                return Ok(());
+            }
+        }
+        self.last_writer
+            .insert(event.question_hash.clone(), timestamp);
+
+        match event.op {
+            GossipOp::Upsert {
+                question,
+                answer,
+                source,
+                embedding,
+            } => self.upsert_local(&event.question_hash, question, answer, source, embedding),
+            GossipOp::Delete => self.remove_local(&event.question_hash),
+        }
+
+        self.store.save_all_qa_items(&self.system.qa_data).await?;
+        Ok(())
+    }
+
+    /// Replaces (or inserts, if absent) the item addressed by `question_hash`
+    /// purely in memory, without persisting or re-broadcasting. Shared by
+    /// `apply_remote_event` and `reload_qa_data`; `add_qa`/`update_qa` do
+    /// their own bookkeeping since they also need to call Gemini for a fresh
+    /// embedding.
+    ///
+    /// `question_hash` is only used to find the existing entry to replace —
+    /// for a gossiped update whose question text changed, it's the *old*
+    /// hash the update was keyed by. The inserted node is always keyed by
+    /// the hash of `question`'s (possibly new) text, since that's what
+    /// `position_of_node` will look it up by afterwards.
+    fn upsert_local(
+        &mut self,
+        question_hash: &str,
+        question: FormattedText,
+        answer: FormattedText,
+        source: Option<String>,
+        embedding: Vec<f64>,
+    ) {
+        let new_hash = utils::get_question_hash(&question.text);
+        let new_item = QAItem {
+            question,
+            answer,
+            source,
+        };
+
+        if let Some(old_node_id) = self.node_by_hash.remove(question_hash) {
+            self.hash_by_node.remove(&old_node_id);
+            self.index.remove(old_node_id);
+        }
+
+        if let Some(position) = self
+            .system
+            .qa_data
+            .iter()
+            .position(|item| utils::get_question_hash(&item.question.text) == question_hash)
+        {
+            self.system.qa_data[position] = new_item;
+            self.system.question_embeddings[position] = embedding.clone();
+        } else {
+            self.system.qa_data.push(new_item);
+            self.system.question_embeddings.push(embedding.clone());
+        }
+
+        let node_id = self.next_node_id;
+        self.next_node_id += 1;
+        self.node_by_hash.insert(new_hash.clone(), node_id);
+        self.hash_by_node.insert(node_id, new_hash);
+        self.index.insert(node_id, embedding);
+        self.maybe_rebuild_index();
+    }
+
+    /// Removes the item addressed by `question_hash` purely in memory.
+    /// Counterpart to `upsert_local`, used by `apply_remote_event` and
+    /// `reload_qa_data`.
+    fn remove_local(&mut self, question_hash: &str) {
+        if let Some(position) = self
+            .system
+            .qa_data
+            .iter()
+            .position(|item| utils::get_question_hash(&item.question.text) == question_hash)
+        {
+            self.system.qa_data.remove(position);
+            self.system.question_embeddings.remove(position);
+        }
+        if let Some(node_id) = self.node_by_hash.remove(question_hash) {
+            self.hash_by_node.remove(&node_id);
+            self.index.remove(node_id);
+        }
+        self.maybe_rebuild_index();
+    }
+
     // --- Accessors for UI/bot logic ---
 
     /// Gets a snapshot of the current QA data.
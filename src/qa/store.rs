@@ -0,0 +1,300 @@
+//! Pluggable storage backend for QA data and the embeddings cache.
+//!
+//! `QAService` talks to persistence exclusively through the [`Store`] trait
+//! so that multiple bot instances can share one QA corpus and embeddings
+//! cache instead of each keeping its own local files. [`LocalFsStore`] wraps
+//! the original JSON-file functions in [`super::persistence`]; [`S3Store`]
+//! keeps the same data in an S3-compatible bucket.
+
+use super::persistence;
+use super::types::QAItem;
+use super::utils;
+use crate::config::{Config, StorageBackend};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// Storage for QA items and their embeddings cache.
+///
+/// Implementations must behave as if freshly created storage is simply
+/// empty (no QA items, empty cache), mirroring the local-file backend's
+/// create-if-missing behavior.
+#[async_trait]
+pub trait Store: Send + Sync {
+    async fn load_qa_items(&self) -> Result<Vec<QAItem>>;
+    async fn save_all_qa_items(&self, items: &[QAItem]) -> Result<()>;
+    async fn load_embeddings_cache(&self) -> Result<HashMap<String, Vec<f64>>>;
+    async fn save_embeddings_cache(&self, cache: &HashMap<String, Vec<f64>>) -> Result<()>;
+    /// Adds a single embedding to the cache. Backends that can address
+    /// entries individually (e.g. object storage) should write just that
+    /// one entry rather than rewriting the whole cache.
+    async fn add_embedding_to_cache(&self, question_text: &str, embedding: Vec<f64>) -> Result<()>;
+}
+
+/// Builds the `Store` selected by `config.storage.backend`.
+pub fn build_store(config: &Arc<Config>) -> Box<dyn Store> {
+    match config.storage.backend {
+        StorageBackend::LocalFs => Box::new(LocalFsStore::new(config.clone())),
+        StorageBackend::S3 => Box::new(S3Store::new(config.clone())),
+        StorageBackend::Sqlite => Box::new(SqliteStore::new(config.clone())),
+    }
+}
+
+/// Wraps the existing local-filesystem JSON functions in [`persistence`].
+pub struct LocalFsStore {
+    config: Arc<Config>,
+}
+
+impl LocalFsStore {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Store for LocalFsStore {
+    async fn load_qa_items(&self) -> Result<Vec<QAItem>> {
+        persistence::load_qa_items(&self.config.qa.qa_json_path)
+    }
+
+    async fn save_all_qa_items(&self, items: &[QAItem]) -> Result<()> {
+        persistence::save_all_qa_items(&self.config.qa.qa_json_path, items)
+    }
+
+    async fn load_embeddings_cache(&self) -> Result<HashMap<String, Vec<f64>>> {
+        let (_, cache) = persistence::load_embeddings_cache(&self.config)?;
+        Ok(cache)
+    }
+
+    async fn save_embeddings_cache(&self, cache: &HashMap<String, Vec<f64>>) -> Result<()> {
+        let cache_path = persistence::get_cache_path(&self.config)?;
+        persistence::save_embeddings_cache(&cache_path, cache)
+    }
+
+    async fn add_embedding_to_cache(&self, question_text: &str, embedding: Vec<f64>) -> Result<()> {
+        persistence::add_embedding_to_cache(&self.config, question_text, embedding)
+    }
+}
+
+/// Stores the same QA JSON and embeddings in an S3-compatible bucket.
+///
+/// Unlike [`LocalFsStore`], a single new embedding is written to its own key
+/// (`embeddings/<model>/<question_hash>`) instead of being folded into one
+/// giant cache object, so concurrent bot instances adding embeddings don't
+/// race on a single read-modify-write.
+pub struct S3Store {
+    config: Arc<Config>,
+    client: OnceCell<aws_sdk_s3::Client>,
+}
+
+impl S3Store {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            config,
+            client: OnceCell::new(),
+        }
+    }
+
+    async fn client(&self) -> &aws_sdk_s3::Client {
+        self.client
+            .get_or_init(|| async {
+                let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+                    .region(aws_sdk_s3::config::Region::new(
+                        self.config.storage.s3_region.clone(),
+                    ));
+                if !self.config.storage.s3_endpoint.is_empty() {
+                    loader = loader.endpoint_url(&self.config.storage.s3_endpoint);
+                }
+                aws_sdk_s3::Client::new(&loader.load().await)
+            })
+            .await
+    }
+
+    fn bucket(&self) -> &str {
+        &self.config.storage.s3_bucket
+    }
+
+    fn qa_items_key(&self) -> String {
+        format!("{}/qa_items.json", self.config.storage.s3_prefix)
+    }
+
+    fn embeddings_prefix(&self) -> String {
+        format!(
+            "{}/embeddings/{}/",
+            self.config.storage.s3_prefix, self.config.embedding.model
+        )
+    }
+
+    fn embedding_key(&self, question_hash: &str) -> String {
+        format!("{}{}", self.embeddings_prefix(), question_hash)
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self
+            .client()
+            .await
+            .get_object()
+            .bucket(self.bucket())
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => {
+                let bytes = output
+                    .body
+                    .collect()
+                    .await
+                    .with_context(|| format!("Failed to read S3 object body for key {key}"))?
+                    .into_bytes();
+                Ok(Some(bytes.to_vec()))
+            }
+            Err(err) if is_not_found(&err) => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed to get S3 object {key}")),
+        }
+    }
+
+    async fn put_object(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.client()
+            .await
+            .put_object()
+            .bucket(self.bucket())
+            .key(key)
+            .body(aws_sdk_s3::primitives::ByteStream::from(body))
+            .send()
+            .await
+            .with_context(|| format!("Failed to put S3 object {key}"))?;
+        Ok(())
+    }
+}
+
+fn is_not_found(err: &aws_sdk_s3::error::SdkError<aws_sdk_s3::operation::get_object::GetObjectError>) -> bool {
+    matches!(
+        err,
+        aws_sdk_s3::error::SdkError::ServiceError(service_err)
+            if service_err.err().is_no_such_key()
+    )
+}
+
+/// Stores embeddings in a local SQLite database instead of a JSON blob; see
+/// [`persistence::load_embeddings_cache_sqlite`] and friends. QA items
+/// themselves are still read from/written to the `qa.qa_json_path` JSON
+/// file, since this backend only replaces the embeddings cache format.
+pub struct SqliteStore {
+    config: Arc<Config>,
+}
+
+impl SqliteStore {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Store for SqliteStore {
+    async fn load_qa_items(&self) -> Result<Vec<QAItem>> {
+        persistence::load_qa_items(&self.config.qa.qa_json_path)
+    }
+
+    async fn save_all_qa_items(&self, items: &[QAItem]) -> Result<()> {
+        persistence::save_all_qa_items(&self.config.qa.qa_json_path, items)
+    }
+
+    async fn load_embeddings_cache(&self) -> Result<HashMap<String, Vec<f64>>> {
+        persistence::load_embeddings_cache_sqlite(&self.config)
+    }
+
+    async fn save_embeddings_cache(&self, cache: &HashMap<String, Vec<f64>>) -> Result<()> {
+        persistence::save_embeddings_cache_sqlite(&self.config, cache)
+    }
+
+    async fn add_embedding_to_cache(&self, question_text: &str, embedding: Vec<f64>) -> Result<()> {
+        persistence::add_embedding_to_cache_sqlite(&self.config, question_text, &embedding)
+    }
+}
+
+#[async_trait]
+impl Store for S3Store {
+    async fn load_qa_items(&self) -> Result<Vec<QAItem>> {
+        match self.get_object(&self.qa_items_key()).await? {
+            Some(bytes) => {
+                serde_json::from_slice(&bytes).context("Failed to deserialize QA JSON from S3")
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn save_all_qa_items(&self, items: &[QAItem]) -> Result<()> {
+        let body = serde_json::to_vec_pretty(items).context("Failed to serialize QA items")?;
+        self.put_object(&self.qa_items_key(), body).await
+    }
+
+    async fn load_embeddings_cache(&self) -> Result<HashMap<String, Vec<f64>>> {
+        let prefix = self.embeddings_prefix();
+        let mut cache = HashMap::new();
+        let mut continuation_token = None;
+
+        loop {
+            let mut request = self
+                .client()
+                .await
+                .list_objects_v2()
+                .bucket(self.bucket())
+                .prefix(&prefix);
+            if let Some(token) = continuation_token.take() {
+                request = request.continuation_token(token);
+            }
+            let response = request
+                .send()
+                .await
+                .context("Failed to list S3 embedding objects")?;
+
+            for object in response.contents() {
+                let Some(key) = object.key() else { continue };
+                let Some(question_hash) = key.strip_prefix(&prefix) else {
+                    continue;
+                };
+                if let Some(bytes) = self.get_object(key).await? {
+                    let embedding: Vec<f64> = serde_json::from_slice(&bytes)
+                        .with_context(|| format!("Failed to deserialize embedding at {key}"))?;
+                    cache.insert(question_hash.to_string(), embedding);
+                }
+            }
+
+            if response.is_truncated() == Some(true) {
+                continuation_token = response.next_continuation_token().map(str::to_string);
+            } else {
+                break;
+            }
+        }
+
+        Ok(cache)
+    }
+
+    async fn save_embeddings_cache(&self, cache: &HashMap<String, Vec<f64>>) -> Result<()> {
+        for (question_hash, embedding) in cache {
+            self.add_embedding_to_cache_raw(question_hash, embedding)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn add_embedding_to_cache(&self, question_text: &str, embedding: Vec<f64>) -> Result<()> {
+        let question_hash = utils::get_question_hash(question_text);
+        self.add_embedding_to_cache_raw(&question_hash, &embedding)
+            .await
+    }
+}
+
+impl S3Store {
+    async fn add_embedding_to_cache_raw(
+        &self,
+        question_hash: &str,
+        embedding: &[f64],
+    ) -> Result<()> {
+        let body = serde_json::to_vec(embedding).context("Failed to serialize embedding")?;
+        self.put_object(&self.embedding_key(question_hash), body)
+            .await
+    }
+}
@@ -45,6 +45,20 @@ pub struct FormattedText {
 pub struct QAItem {
     pub question: FormattedText,
     pub answer: FormattedText,
+    /// `None` for a hand-authored Q&A pair. `Some(document_name)` marks this
+    /// item as an ingested passage: `question` holds the chunk text (what
+    /// gets embedded and matched against) rather than a real question, and
+    /// `answer` mirrors it back since there is no authored answer to give.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+impl QAItem {
+    /// `true` if this item came from document ingestion rather than being
+    /// hand-authored via `/addqa`.
+    pub fn is_passage(&self) -> bool {
+        self.source.is_some()
+    }
 }
 
 /// Represents the core data of the Question-Answering system.
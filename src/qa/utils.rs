@@ -1,3 +1,4 @@
+use super::types::QAItem;
 use sha2::{Digest, Sha256};
 
 /// 计算问题的 SHA256 哈希值
@@ -6,3 +7,17 @@ pub fn get_question_hash(question: &str) -> String {
     hasher.update(question.as_bytes());
     format!("{:x}", hasher.finalize())
 }
+
+/// Fingerprints `qa_data` by its ordered sequence of question hashes, so a
+/// persisted HNSW graph (whose node ids are assigned by iteration order over
+/// this same slice, see `QAService::rebuild_index`) can be checked for an
+/// exact content match before being reused, not just a matching node count.
+/// Any add/remove/reorder/question-edit changes the fingerprint.
+pub fn corpus_fingerprint(qa_data: &[QAItem]) -> String {
+    let mut hasher = Sha256::new();
+    for item in qa_data {
+        hasher.update(get_question_hash(&item.question.text).as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
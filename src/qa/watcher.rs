@@ -0,0 +1,84 @@
+//! src/qa/watcher.rs
+//!
+//! Polls the config file and `config.qa.qa_json_path` for modifications and
+//! hot-reloads whichever one changed, so tuning `similarity.threshold`,
+//! `telegram.allowed_group_ids`, or editing the QA corpus by hand no longer
+//! requires restarting the bot. Polling rather than OS-level file-system
+//! notifications, consistent with this codebase's preference (see
+//! `gossip.rs`) for a simple, dependency-free implementation over pulling in
+//! a new crate for something that only needs to run a few times a minute.
+
+use super::service::QAService;
+use crate::config;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::Mutex;
+use tokio::time::{Duration, interval};
+
+/// How often to check the watched files' modification times.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+/// Spawns a background task that polls the config file and QA JSON file
+/// every [`POLL_INTERVAL`] and reloads whichever one changed on disk. Runs
+/// for the lifetime of the process. A config file that can't be read or
+/// parsed is logged and the previous, still-good configuration is kept. A
+/// QA reload that fails partway (e.g. on an embedding error) is logged, and
+/// the entries it had already applied stay applied.
+pub fn start(config_path: PathBuf, qa_service: Arc<Mutex<QAService>>) {
+    tokio::spawn(async move {
+        let mut config_mtime = modified_at(&config_path);
+        let mut qa_mtime = {
+            let qa_path = qa_service.lock().await.config.qa.qa_json_path.clone();
+            modified_at(Path::new(&qa_path))
+        };
+
+        let mut ticker = interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+
+            let current_config_mtime = modified_at(&config_path);
+            if current_config_mtime.is_some() && current_config_mtime != config_mtime {
+                config_mtime = current_config_mtime;
+                // Parsed directly rather than via `config::load_user_config`,
+                // which replaces an unparsable file with defaults.
+                let parsed = std::fs::read_to_string(&config_path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|contents| {
+                        toml::from_str::<config::Config>(&contents).map_err(anyhow::Error::from)
+                    });
+                match parsed {
+                    Ok(new_config) => {
+                        qa_service.lock().await.reload_config(new_config);
+                        log::info!(
+                            "Hot-reloaded configuration from {}",
+                            config_path.display()
+                        );
+                    }
+                    Err(e) => log::warn!("Failed to hot-reload configuration: {}", e),
+                }
+            }
+
+            let qa_path = qa_service.lock().await.config.qa.qa_json_path.clone();
+            let current_qa_mtime = modified_at(Path::new(&qa_path));
+            if current_qa_mtime.is_some() && current_qa_mtime != qa_mtime {
+                qa_mtime = current_qa_mtime;
+                match qa_service.lock().await.reload_qa_data().await {
+                    Ok(summary) => log::info!(
+                        "Hot-reloaded QA data from {}: {} added, {} updated, {} removed, {} unchanged",
+                        qa_path,
+                        summary.added,
+                        summary.updated,
+                        summary.removed,
+                        summary.unchanged
+                    ),
+                    Err(e) => log::warn!("Failed to hot-reload QA data from {}: {}", qa_path, e),
+                }
+            }
+        }
+    });
+}
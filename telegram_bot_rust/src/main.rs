@@ -20,6 +20,12 @@ pub struct Config {
     pub similarity_threshold: f32,
     pub delete_delay: u64, // Added
     pub message_timeout: u64, // Added
+    /// Fixed mean/sigma for the affine score-normalization applied in
+    /// `qa::QAEmbedding::find_matching_qa` before thresholding. When either
+    /// is unset, the mean/sigma are instead estimated online from observed
+    /// query scores for `embed_model`. See `SCORE_NORM_MEAN`/`SCORE_NORM_SIGMA`.
+    pub score_norm_mean: Option<f32>,
+    pub score_norm_sigma: Option<f32>,
 }
 
 // Basic command for testing
@@ -117,6 +123,13 @@ async fn main() -> Result<(), anyhow::Error> {
     let message_timeout_str = std::env::var("MESSAGE_TIMEOUT_SECS").unwrap_or_else(|_| "120".to_string());
     let message_timeout = message_timeout_str.parse().with_context(|| format!("Failed to parse MESSAGE_TIMEOUT_SECS: '{}'", message_timeout_str))?;
 
+    let score_norm_mean = std::env::var("SCORE_NORM_MEAN").ok().and_then(|v| {
+        v.parse().map_err(|e| log::warn!("Failed to parse SCORE_NORM_MEAN: '{}': {}", v, e)).ok()
+    });
+    let score_norm_sigma = std::env::var("SCORE_NORM_SIGMA").ok().and_then(|v| {
+        v.parse().map_err(|e| log::warn!("Failed to parse SCORE_NORM_SIGMA: '{}': {}", v, e)).ok()
+    });
+
     let config = Arc::new(Config {
         api_key,
         embed_api_url,
@@ -126,6 +139,8 @@ async fn main() -> Result<(), anyhow::Error> {
         similarity_threshold: 0.75f32, // Could also be from env var
         delete_delay,
         message_timeout,
+        score_norm_mean,
+        score_norm_sigma,
     });
 
     log::info!("Configuration loaded successfully: {:?}", config); // Be careful logging sensitive parts of config like API keys or full tokens.
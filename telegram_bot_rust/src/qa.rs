@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 // use std::io::{Read, Write}; // Read/Write not directly needed for fs::read_to_string and fs::write with strings
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
 use anyhow::{Context, anyhow}; // anyhow::Context is already imported here
 use sha2::{Digest, Sha256};
 // Removed: use std::net::TcpStream; // No longer using manual TCP client
@@ -10,6 +13,11 @@ use sha2::{Digest, Sha256};
 #[derive(Deserialize, Debug)]
 struct EmbeddingData { // As per subtask item 2
     embedding: Vec<f32>,
+    /// Position of this item within the batch's `"input"` array. Present so
+    /// a batched response can be mapped back to the question it answers
+    /// even if the API doesn't preserve input order.
+    #[serde(default)]
+    index: usize,
 }
 
 #[derive(Deserialize, Debug)]
@@ -23,10 +31,58 @@ pub struct QAItem {
     pub answer: String,
 }
 
+/// Maximum number of questions sent to the embeddings API in a single
+/// request. Keeps batches well clear of typical payload/token limits while
+/// still cutting a large QA set's round-trip count by roughly this factor.
+const EMBEDDING_BATCH_SIZE: usize = 64;
+
+/// Running mean/variance of observed query similarity scores, updated via
+/// Welford's online algorithm so [`QAEmbedding::find_matching_qa`] can
+/// normalize scores into a comparable 0..=1 band even when `Config` doesn't
+/// pin a `score_norm_mean`/`score_norm_sigma` for the current embedding model.
+#[derive(Debug, Default)]
+struct ScoreStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+/// Below this many observed scores, the online mean/sigma estimate is too
+/// noisy to normalize against, so [`QAEmbedding::normalize_score`] falls back
+/// to the raw similarity.
+const MIN_SAMPLES_FOR_NORMALIZATION: u64 = 10;
+
+impl ScoreStats {
+    fn observe(&mut self, score: f32) {
+        self.count += 1;
+        let delta = score as f64 - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = score as f64 - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    /// Sample mean/standard deviation, or `None` until enough samples have
+    /// been observed to estimate them meaningfully.
+    fn mean_and_sigma(&self) -> Option<(f32, f32)> {
+        if self.count < MIN_SAMPLES_FOR_NORMALIZATION {
+            return None;
+        }
+        let variance = self.m2 / (self.count - 1) as f64;
+        let sigma = variance.sqrt();
+        if sigma < f64::EPSILON {
+            return None;
+        }
+        Some((self.mean as f32, sigma as f32))
+    }
+}
+
 #[derive(Debug)]
 pub struct QAEmbedding {
     pub qa_data: Vec<QAItem>,
     pub question_embeddings: Vec<Vec<f32>>,
+    /// Online estimate of the query-score distribution, used by
+    /// `normalize_score` when `Config` doesn't pin a mean/sigma.
+    score_stats: Mutex<ScoreStats>,
 }
 
 impl QAEmbedding {
@@ -34,6 +90,28 @@ impl QAEmbedding {
         QAEmbedding {
             qa_data: Vec::new(),
             question_embeddings: Vec::new(),
+            score_stats: Mutex::new(ScoreStats::default()),
+        }
+    }
+
+    /// Rescales a raw cosine similarity into a comparable 0..=1 "confidence"
+    /// band via an affine distribution shift: `(s - mean) / sigma * 0.5 + 0.5`,
+    /// clamped to `0..=1`. Uses `config.score_norm_mean`/`config.score_norm_sigma`
+    /// when both are set, else the mean/sigma estimated online from past query
+    /// scores; falls back to the raw score until enough samples have been
+    /// observed (or if sigma would be ~0).
+    fn normalize_score(&self, score: f32, config: &crate::Config) -> f32 {
+        let mut stats = self.score_stats.lock().unwrap();
+        stats.observe(score);
+
+        let params = match (config.score_norm_mean, config.score_norm_sigma) {
+            (Some(mean), Some(sigma)) if sigma.abs() > f32::EPSILON => Some((mean, sigma)),
+            _ => stats.mean_and_sigma(),
+        };
+
+        match params {
+            Some((mean, sigma)) => (((score - mean) / sigma) * 0.5 + 0.5).clamp(0.0, 1.0),
+            None => score,
         }
     }
 
@@ -51,10 +129,6 @@ impl QAEmbedding {
             .with_context(|| format!("Failed to deserialize QA JSON from: {}", qa_json_path))?;
         log::info!("Successfully loaded {} QA items from {}", self.qa_data.len(), qa_json_path);
 
-        let current_qa_hash = calculate_qa_hash(&self.qa_data)
-            .context("Failed to calculate QA hash")?;
-        log::debug!("Calculated QA hash for '{}': {}", qa_json_path, current_qa_hash);
-
         let model_name_sanitized = config.embed_model.replace(|c: char| !c.is_alphanumeric(), "_");
         let cache_dir = Path::new(&config.cache_dir);
 
@@ -63,24 +137,44 @@ impl QAEmbedding {
         let embeddings_cache_file_name = format!("embeddings_cache_{}.json", model_name_sanitized);
         let embeddings_cache_file_path = cache_dir.join(embeddings_cache_file_name);
 
-        match load_cached_embeddings(&embeddings_cache_file_path, &current_qa_hash)? {
-            Some(cached_embeddings) => {
-                self.question_embeddings = cached_embeddings;
-                log::info!("Successfully loaded {} embeddings from cache: {:?}", self.question_embeddings.len(), embeddings_cache_file_path);
-                return Ok(());
-            }
-            None => {
-                log::info!("No valid cache found or cache is stale for {}. Generating new embeddings...", embeddings_cache_file_path.display());
+        // Per-question cache keyed by `get_question_hash`, so editing one QA
+        // pair only invalidates that pair's embedding instead of the whole set.
+        let mut cache = load_embeddings_cache(&embeddings_cache_file_path)?;
+
+        let mut embeddings = Vec::with_capacity(self.qa_data.len());
+        let mut missing_indices = Vec::new();
+        for (index, item) in self.qa_data.iter().enumerate() {
+            match cache.get(&get_question_hash(&item.question)) {
+                Some(cached) => embeddings.push(cached.clone()),
+                None => {
+                    embeddings.push(Vec::new()); // placeholder, filled in below
+                    missing_indices.push(index);
+                }
             }
         }
 
-        log::info!("Generating {} new embeddings for {} items...", self.qa_data.len(), qa_json_path);
-        let mut new_embeddings = Vec::new();
-        for (index, qa_item) in self.qa_data.iter().enumerate() {
-            // Old: print!("Fetching embedding for Q{}: {}... ", index + 1, qa_item.question.chars().take(50).collect::<String>());
-            log::info!("Fetching embedding for Q{}/{}: '{}'...", index + 1, self.qa_data.len(), qa_item.question.chars().take(70).collect::<String>());
-            match get_embedding( // Use new async get_embedding
-                &qa_item.question,
+        if missing_indices.is_empty() {
+            log::info!(
+                "All {} embeddings found in cache: {:?}. No API calls needed.",
+                self.qa_data.len(), embeddings_cache_file_path
+            );
+            self.question_embeddings = embeddings;
+            return Ok(());
+        }
+
+        log::info!(
+            "{} of {} embeddings missing from cache; generating in batches of {}...",
+            missing_indices.len(), self.qa_data.len(), EMBEDDING_BATCH_SIZE
+        );
+        let batches: Vec<&[usize]> = missing_indices.chunks(EMBEDDING_BATCH_SIZE).collect();
+        for (batch_index, batch) in batches.iter().enumerate() {
+            let questions: Vec<String> = batch.iter().map(|&i| self.qa_data[i].question.clone()).collect();
+            log::info!(
+                "Fetching embeddings for batch {}/{} ({} questions)...",
+                batch_index + 1, batches.len(), questions.len()
+            );
+            match get_embeddings_batch( // Use new async get_embeddings_batch
+                &questions,
                 &config.api_key,
                 &config.embed_api_url,
                 &config.embed_model,
@@ -88,30 +182,28 @@ impl QAEmbedding {
             )
             .await // await the async call
             {
-                Ok(embedding) => {
-                    println!("Ok ({} dims)", embedding.len());
-                    new_embeddings.push(embedding);
+                Ok(batch_embeddings) => {
+                    for (&index, embedding) in batch.iter().zip(batch_embeddings.into_iter()) {
+                        cache.insert(get_question_hash(&self.qa_data[index].question), embedding.clone());
+                        embeddings[index] = embedding;
+                    }
                 }
                 Err(e) => {
-                    // Log the specific error and the question that failed.
-                    log::error!("Failed to get embedding for Q{}: '{}'. Error: {:?}", index + 1, qa_item.question, e);
-                    // Current behavior: fail entire process if one embedding fails. Acceptable for now.
+                    // Log the specific error and the batch that failed.
+                    log::error!("Failed to get embeddings for batch {}/{}. Error: {:?}", batch_index + 1, batches.len(), e);
+                    // Current behavior: fail entire process if one batch fails. Acceptable for now.
                     return Err(e).context(format!(
-                        "Failed to get embedding for question Q{}: '{}'",
-                        index + 1, qa_item.question
+                        "Failed to get embeddings for batch {}/{}",
+                        batch_index + 1, batches.len()
                     ));
                 }
             }
         }
-        self.question_embeddings = new_embeddings;
-        log::info!("Successfully generated {} new embeddings.", self.question_embeddings.len());
+        self.question_embeddings = embeddings;
+        log::info!("Successfully generated {} new embeddings.", missing_indices.len());
 
-        save_embeddings_cache(
-            &embeddings_cache_file_path,
-            &current_qa_hash,
-            &self.question_embeddings,
-        )
-        .with_context(|| format!("Failed to save new embeddings to cache: {:?}", embeddings_cache_file_path))?;
+        save_embeddings_cache(&embeddings_cache_file_path, &cache)
+            .with_context(|| format!("Failed to save updated embeddings cache: {:?}", embeddings_cache_file_path))?;
 
         Ok(())
     }
@@ -156,14 +248,18 @@ impl QAEmbedding {
             }
         }
 
-        log::info!("Query: '{}', Max similarity: {:.4}", text.chars().take(70).collect::<String>(), max_similarity);
+        let confidence = self.normalize_score(max_similarity, config);
+        log::info!(
+            "Query: '{}', Max similarity: {:.4}, Normalized confidence: {:.4}",
+            text.chars().take(70).collect::<String>(), max_similarity, confidence
+        );
 
         if let Some(index) = best_match_index {
-            if max_similarity >= config.similarity_threshold {
-                log::info!("Match found for query '{}': Q{}/{} ('{}') with similarity {:.4}", text.chars().take(70).collect::<String>(), index + 1, self.qa_data.len(), self.qa_data[index].question.chars().take(70).collect::<String>(), max_similarity);
+            if confidence >= config.similarity_threshold {
+                log::info!("Match found for query '{}': Q{}/{} ('{}') with confidence {:.4}", text.chars().take(70).collect::<String>(), index + 1, self.qa_data.len(), self.qa_data[index].question.chars().take(70).collect::<String>(), confidence);
                 Ok(Some(self.qa_data[index].clone()))
             } else {
-                log::info!("Max similarity {:.4} is below threshold {:.4} for query: '{}'", max_similarity, config.similarity_threshold, text.chars().take(70).collect::<String>());
+                log::info!("Confidence {:.4} is below threshold {:.4} for query: '{}'", confidence, config.similarity_threshold, text.chars().take(70).collect::<String>());
                 Ok(None)
             }
         } else {
@@ -174,61 +270,166 @@ impl QAEmbedding {
     }
 }
 
-pub fn calculate_qa_hash(qa_data: &Vec<QAItem>) -> Result<String, anyhow::Error> {
-    let json_string = serde_json::to_string(qa_data)
-        .context("Failed to serialize QAData for hashing")?;
+/// Returns a stable hex-encoded SHA-256 hash of a question's text, used to
+/// key the per-question embeddings cache so editing one QA pair's answer (or
+/// adding/removing pairs) doesn't invalidate every other pair's cached
+/// embedding.
+pub fn get_question_hash(question: &str) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(json_string.as_bytes());
-    let result = hasher.finalize();
-    Ok(format!("{:x}", result)) // Wrapped in Ok()
+    hasher.update(question.as_bytes());
+    format!("{:x}", hasher.finalize())
 }
 
-pub fn load_cached_embeddings(
-    cache_file_path: &Path,
-    expected_qa_hash: &str,
-) -> Result<Option<Vec<Vec<f32>>>, anyhow::Error> {
-    let hash_file_path = cache_file_path.with_extension("hash");
-    if !cache_file_path.exists() || !hash_file_path.exists() {
-        log::info!("Cache file or hash file not found for: {:?}", cache_file_path.display());
-        return Ok(None);
-    }
-    let cached_hash = fs::read_to_string(&hash_file_path)
-        .with_context(|| format!("Failed to read hash file: {:?}", hash_file_path.display()))?;
-    if cached_hash.trim() != expected_qa_hash {
-        log::info!("Cache is stale (hash mismatch) for {:?}. Expected: {}, Found: {}", cache_file_path.display(), expected_qa_hash, cached_hash.trim());
-        // Optionally: Delete stale cache files
-        // fs::remove_file(cache_file_path).ok();
-        // fs::remove_file(hash_file_path).ok();
-        return Ok(None);
+/// Loads the per-question embeddings cache, keyed by [`get_question_hash`].
+/// A missing or unparsable cache file is treated as an empty cache rather
+/// than a hard error, so a fresh deployment or a corrupted cache file doesn't
+/// prevent startup.
+pub fn load_embeddings_cache(cache_file_path: &Path) -> Result<HashMap<String, Vec<f32>>, anyhow::Error> {
+    if !cache_file_path.exists() {
+        log::info!("No embeddings cache file found at {:?}. Starting with an empty cache.", cache_file_path.display());
+        return Ok(HashMap::new());
     }
     let file = fs::File::open(cache_file_path)
         .with_context(|| format!("Failed to open cache file: {:?}", cache_file_path.display()))?;
-    let embeddings: Vec<Vec<f32>> = serde_json::from_reader(std::io::BufReader::new(file))
-        .with_context(|| format!("Failed to deserialize embeddings from cache file: {:?}", cache_file_path.display()))?;
-    // log::info! is now in the caller `load_and_embed_qa`
-    Ok(Some(embeddings))
+    let cache = serde_json::from_reader(std::io::BufReader::new(file)).unwrap_or_else(|e| {
+        log::warn!("Failed to parse embeddings cache file at {:?}, starting fresh: {}", cache_file_path.display(), e);
+        HashMap::new()
+    });
+    Ok(cache)
 }
 
 pub fn save_embeddings_cache(
     cache_file_path: &Path,
-    qa_hash: &str,
-    embeddings: &Vec<Vec<f32>>,
+    cache: &HashMap<String, Vec<f32>>,
 ) -> Result<(), anyhow::Error> {
     if let Some(parent_dir) = cache_file_path.parent() {
         fs::create_dir_all(parent_dir).with_context(|| format!("Failed to create cache directory: {:?}", parent_dir.display()))?;
     }
-    let json_string = serde_json::to_string_pretty(embeddings)
-        .context("Failed to serialize embeddings to JSON for saving to cache")?;
+    let json_string = serde_json::to_string_pretty(cache)
+        .context("Failed to serialize embeddings cache to JSON for saving")?;
     fs::write(cache_file_path, json_string)
-        .with_context(|| format!("Failed to write embeddings to cache file: {:?}", cache_file_path.display()))?;
-    let hash_file_path = cache_file_path.with_extension("hash");
-    fs::write(&hash_file_path, qa_hash)
-        .with_context(|| format!("Failed to write hash to file: {:?}", hash_file_path.display()))?;
-    log::info!("Saved {} embeddings to cache: {:?}", embeddings.len(), cache_file_path.display());
+        .with_context(|| format!("Failed to write embeddings cache file: {:?}", cache_file_path.display()))?;
+    log::info!("Saved {} embeddings to cache: {:?}", cache.len(), cache_file_path.display());
     Ok(())
 }
 
-// New async get_embedding using reqwest
+/// Cap on attempts [`request_embeddings_with_retry`] makes before giving up
+/// and surfacing the last error.
+const MAX_EMBEDDING_ATTEMPTS: u32 = 5;
+
+/// A failed embedding-API attempt, classified so the retry loop knows how
+/// long to back off, or whether to give up immediately.
+enum EmbeddingAttemptError {
+    /// Timeout, connection error, or 5xx — likely to succeed on retry.
+    Transient(anyhow::Error),
+    /// HTTP 429, carrying the server's `Retry-After` hint if it sent one.
+    RateLimited {
+        retry_after: Option<Duration>,
+        source: anyhow::Error,
+    },
+    /// Any other 4xx — retrying won't help.
+    Fatal(anyhow::Error),
+}
+
+/// Performs a single POST to the embeddings API and parses its response,
+/// classifying failures for [`request_embeddings_with_retry`].
+async fn try_request_embeddings(
+    client: &reqwest::Client,
+    embed_api_url: &str,
+    api_key: &str,
+    payload: &serde_json::Value,
+) -> Result<EmbeddingResponse, EmbeddingAttemptError> {
+    let response = client
+        .post(embed_api_url)
+        .bearer_auth(api_key)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| {
+            EmbeddingAttemptError::Transient(anyhow::Error::new(e).context(format!(
+                "Failed to send request to embedding API URL: {}",
+                embed_api_url
+            )))
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_secs);
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("Could not read error body: {}", e));
+        let source = anyhow!(
+            "Embedding API request failed with status {}: {}",
+            status,
+            error_body
+        );
+
+        return Err(if status.as_u16() == 429 {
+            EmbeddingAttemptError::RateLimited { retry_after, source }
+        } else if status.is_server_error() {
+            EmbeddingAttemptError::Transient(source)
+        } else {
+            EmbeddingAttemptError::Fatal(source)
+        });
+    }
+
+    response.json().await.map_err(|e| {
+        EmbeddingAttemptError::Transient(
+            anyhow::Error::new(e).context("Failed to deserialize JSON response from embedding API"),
+        )
+    })
+}
+
+/// Retries [`try_request_embeddings`] with backoff: a transient failure
+/// (timeout, 5xx) waits `10^attempt` ms before the next try; HTTP 429 waits
+/// for the response's `Retry-After` header if present, else
+/// `100 + 10^attempt` ms; any other 4xx gives up immediately. Gives up after
+/// [`MAX_EMBEDDING_ATTEMPTS`] and surfaces the last error.
+async fn request_embeddings_with_retry(
+    client: &reqwest::Client,
+    embed_api_url: &str,
+    api_key: &str,
+    payload: &serde_json::Value,
+) -> Result<EmbeddingResponse, anyhow::Error> {
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        let (wait, source) = match try_request_embeddings(client, embed_api_url, api_key, payload).await
+        {
+            Ok(response) => return Ok(response),
+            Err(EmbeddingAttemptError::Fatal(e)) => {
+                return Err(e.context("Embedding API request failed with a non-retryable client error"));
+            }
+            Err(EmbeddingAttemptError::Transient(e)) => {
+                (Duration::from_millis(10u64.pow(attempt)), e)
+            }
+            Err(EmbeddingAttemptError::RateLimited { retry_after, source }) => (
+                retry_after.unwrap_or_else(|| Duration::from_millis(100 + 10u64.pow(attempt))),
+                source,
+            ),
+        };
+
+        if attempt >= MAX_EMBEDDING_ATTEMPTS {
+            return Err(source.context(format!(
+                "Embedding API request failed after {} attempts",
+                attempt
+            )));
+        }
+        log::warn!(
+            "Embedding API attempt {}/{} failed: {:?}. Retrying in {:?}...",
+            attempt, MAX_EMBEDDING_ATTEMPTS, source, wait
+        );
+        tokio::time::sleep(wait).await;
+    }
+}
+
+// New async get_embedding using reqwest, with retry/backoff via request_embeddings_with_retry
 pub async fn get_embedding(
     text: &str,
     api_key: &str,
@@ -243,29 +444,7 @@ pub async fn get_embedding(
     });
     log::trace!("Embedding request payload: {:?}", payload);
 
-    let response = client
-        .post(embed_api_url)
-        .bearer_auth(api_key)
-        .json(&payload)
-        .send()
-        .await
-        .with_context(|| format!("Failed to send request to embedding API URL: {}", embed_api_url))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_body = response.text().await.unwrap_or_else(|e| format!("Could not read error body: {}", e));
-        log::error!("Embedding API request failed with status {}. Response body: {}", status, error_body);
-        return Err(anyhow!(
-            "Embedding API request failed with status {}: {}",
-            status,
-            error_body
-        ));
-    }
-
-    let parsed_response: EmbeddingResponse = response
-        .json()
-        .await
-        .context("Failed to deserialize JSON response from embedding API")?;
+    let parsed_response = request_embeddings_with_retry(client, embed_api_url, api_key, &payload).await?;
 
     log::trace!("Received embedding API response: {:?}", parsed_response);
 
@@ -276,6 +455,43 @@ pub async fn get_embedding(
     }
 }
 
+// Batched async get_embeddings using reqwest, with retry/backoff via request_embeddings_with_retry
+pub async fn get_embeddings_batch(
+    texts: &[String],
+    api_key: &str,
+    embed_api_url: &str,
+    embed_model: &str,
+    client: &reqwest::Client,
+) -> Result<Vec<Vec<f32>>, anyhow::Error> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    log::debug!("Requesting {} embeddings in one batch with model: {}", texts.len(), embed_model);
+    let payload = serde_json::json!({
+        "model": embed_model,
+        "input": texts
+    });
+    log::trace!("Batch embedding request payload: {:?}", payload);
+
+    let mut parsed_response =
+        request_embeddings_with_retry(client, embed_api_url, api_key, &payload).await?;
+
+    if parsed_response.data.len() != texts.len() {
+        return Err(anyhow!(
+            "Batch embedding API returned {} embeddings for {} inputs",
+            parsed_response.data.len(),
+            texts.len()
+        ));
+    }
+
+    // Responses aren't guaranteed to preserve input order, so sort each
+    // item back into place by its `index` before handing the embeddings
+    // back positionally.
+    parsed_response.data.sort_by_key(|d| d.index);
+    Ok(parsed_response.data.into_iter().map(|d| d.embedding).collect())
+}
+
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.is_empty() || b.is_empty() || a.len() != b.len() {
         return 0.0; // Or handle error: dimensions mismatch or empty vectors
@@ -304,11 +520,12 @@ pub fn format_answer_html(answer: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*; // Imports items from the parent module (qa.rs)
+    use crate::Config;
     use std::fs; // `File` and `Write` are not directly used by name in tests after review
     use std::path::Path;
 
     // Helper function to create a dummy Config for tests if needed
-    // For functions like load_cached_embeddings or save_embeddings_cache,
+    // For functions like load_embeddings_cache or save_embeddings_cache,
     // file paths are constructed, so actual Config might not be strictly needed
     // if paths are directly provided or mocked.
     // For `find_matching_qa` or `load_and_embed_qa` if they were to be unit tested
@@ -357,29 +574,76 @@ mod tests {
     }
 
     #[test]
-    fn test_calculate_qa_hash_consistency() {
-        let qa_items = vec![
-            QAItem { question: "q1".to_string(), answer: "a1".to_string() },
-            QAItem { question: "q2".to_string(), answer: "a2".to_string() },
-        ];
-        let hash1 = calculate_qa_hash(&qa_items).unwrap();
-        let hash2 = calculate_qa_hash(&qa_items).unwrap();
-        assert_eq!(hash1, hash2);
+    fn test_score_stats_insufficient_samples_returns_none() {
+        let mut stats = ScoreStats::default();
+        for _ in 0..(MIN_SAMPLES_FOR_NORMALIZATION - 1) {
+            stats.observe(0.5);
+        }
+        assert!(stats.mean_and_sigma().is_none());
+    }
+
+    #[test]
+    fn test_score_stats_mean_and_sigma() {
+        let mut stats = ScoreStats::default();
+        for score in [0.2f32, 0.4, 0.6, 0.8, 0.2, 0.4, 0.6, 0.8, 0.2, 0.4] {
+            stats.observe(score);
+        }
+        let (mean, sigma) = stats.mean_and_sigma().expect("should have enough samples");
+        assert!((mean - 0.46).abs() < 1e-2, "mean was {}", mean);
+        assert!(sigma > 0.0, "sigma was {}", sigma);
     }
 
     #[test]
-    fn test_calculate_qa_hash_sensitivity() {
-        let qa_items1 = vec![QAItem { question: "q1".to_string(), answer: "a1".to_string() }];
-        let qa_items2 = vec![QAItem { question: "q2".to_string(), answer: "a1".to_string() }]; // Different question
-        let qa_items3 = vec![QAItem { question: "q1".to_string(), answer: "a2".to_string() }]; // Different answer
+    fn test_normalize_score_falls_back_to_raw_before_enough_samples() {
+        let qa = QAEmbedding::new();
+        let config = Config {
+            api_key: String::new(),
+            embed_api_url: String::new(),
+            embed_model: String::new(),
+            cache_dir: String::new(),
+            token: String::new(),
+            similarity_threshold: 0.75,
+            delete_delay: 0,
+            message_timeout: 0,
+            score_norm_mean: None,
+            score_norm_sigma: None,
+        };
+        assert_eq!(qa.normalize_score(0.9, &config), 0.9);
+    }
 
-        let hash1 = calculate_qa_hash(&qa_items1).unwrap();
-        let hash2 = calculate_qa_hash(&qa_items2).unwrap();
-        let hash3 = calculate_qa_hash(&qa_items3).unwrap();
+    #[test]
+    fn test_normalize_score_uses_configured_mean_and_sigma() {
+        let qa = QAEmbedding::new();
+        let config = Config {
+            api_key: String::new(),
+            embed_api_url: String::new(),
+            embed_model: String::new(),
+            cache_dir: String::new(),
+            token: String::new(),
+            similarity_threshold: 0.75,
+            delete_delay: 0,
+            message_timeout: 0,
+            score_norm_mean: Some(0.5),
+            score_norm_sigma: Some(0.1),
+        };
+        // score == mean -> confidence 0.5
+        assert!((qa.normalize_score(0.5, &config) - 0.5).abs() < 1e-6);
+        // two sigma above the mean -> confidence clamped to 1.0
+        assert_eq!(qa.normalize_score(0.7, &config), 1.0);
+    }
 
+    #[test]
+    fn test_get_question_hash_consistency() {
+        let hash1 = get_question_hash("q1");
+        let hash2 = get_question_hash("q1");
+        assert_eq!(hash1, hash2);
+    }
+
+    #[test]
+    fn test_get_question_hash_sensitivity() {
+        let hash1 = get_question_hash("q1");
+        let hash2 = get_question_hash("q2");
         assert_ne!(hash1, hash2);
-        assert_ne!(hash1, hash3);
-        assert_ne!(hash2, hash3); // Also ensure these are different
     }
 
     // Helper to ensure test cache directory exists and is clean
@@ -395,7 +659,7 @@ mod tests {
     #[test]
     fn test_embedding_cache_save_and_load() {
         let cache_dir_base = "target/test_cache"; // Base for all cache tests
-        let specific_test_cache_dir = Path::new(cache_dir_base).join("embeddings_cache_test");
+        let specific_test_cache_dir = Path::new(cache_dir_base).join("embeddings_cache_per_item_test");
         setup_test_cache_dir(specific_test_cache_dir.to_str().unwrap()).expect("Failed to set up test cache directory");
 
         let model_name = "test_model_cache";
@@ -403,33 +667,27 @@ mod tests {
         let cache_file_name = format!("embeddings_cache_{}.json", model_name);
         let cache_file_path = specific_test_cache_dir.join(cache_file_name);
 
-        let embeddings: Vec<Vec<f32>> = vec![vec![1.0, 2.0, 0.5], vec![3.0, 4.0, 1.5]];
-        let qa_hash = "test_hash_abc_123";
+        let mut cache: HashMap<String, Vec<f32>> = HashMap::new();
+        cache.insert(get_question_hash("q1"), vec![1.0, 2.0, 0.5]);
+        cache.insert(get_question_hash("q2"), vec![3.0, 4.0, 1.5]);
 
         // Test saving
-        save_embeddings_cache(&cache_file_path, qa_hash, &embeddings).unwrap();
+        save_embeddings_cache(&cache_file_path, &cache).unwrap();
         assert!(cache_file_path.exists(), "Cache file should be created");
 
-        let hash_file_path = cache_file_path.with_extension("hash");
-        assert!(hash_file_path.exists(), "Hash file should be created");
-        let saved_hash = std::fs::read_to_string(hash_file_path).unwrap();
-        assert_eq!(saved_hash, qa_hash, "Saved hash should match original hash");
-
-        // Test loading with correct hash
-        let loaded_embeddings = load_cached_embeddings(&cache_file_path, qa_hash)
-            .unwrap()
-            .expect("Should load embeddings with correct hash");
-        assert_eq!(loaded_embeddings, embeddings, "Loaded embeddings should match saved ones");
-
-        // Test loading with incorrect hash
-        let incorrect_hash = "incorrect_hash_xyz_789";
-        let result_incorrect_hash = load_cached_embeddings(&cache_file_path, incorrect_hash).unwrap();
-        assert!(result_incorrect_hash.is_none(), "Should return None for incorrect hash");
+        // Test loading
+        let loaded_cache = load_embeddings_cache(&cache_file_path).unwrap();
+        assert_eq!(loaded_cache, cache, "Loaded cache should match saved one");
+        assert_eq!(
+            loaded_cache.get(&get_question_hash("q1")),
+            Some(&vec![1.0, 2.0, 0.5]),
+            "Loaded cache should be keyed by question hash"
+        );
 
-        // Test loading when cache file is missing (after deleting it)
+        // Test loading when cache file is missing: should return an empty cache.
         std::fs::remove_file(&cache_file_path).unwrap();
-        let result_missing_file = load_cached_embeddings(&cache_file_path, qa_hash).unwrap();
-        assert!(result_missing_file.is_none(), "Should return None if cache file is missing");
+        let result_missing_file = load_embeddings_cache(&cache_file_path).unwrap();
+        assert!(result_missing_file.is_empty(), "Should return an empty cache if the file is missing");
 
         // Clean up: remove the specific test_cache_dir.
         // If using a common base like "target/test_cache", be careful if other tests use it.